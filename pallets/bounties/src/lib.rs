@@ -0,0 +1,814 @@
+// Copyright 2021 LISTEN Developer.
+// This file is part of LISTEN.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Room Bounties
+//!
+//! Rooms post tasks with a reward, paid out of the room's treasury pot.
+//!
+//! Anyone can propose a bounty for a room by reserving a bond. Once the room's `ApproveOrigin`
+//! approves it, its value is taken from the room's free amount and moved to the bounty's own
+//! account, and the bounty is funded. A curator is then proposed with a fee; by accepting, the
+//! curator reserves a deposit of `CuratorDepositMultiplier` of the fee and becomes responsible
+//! for the bounty. The curator awards the bounty to a beneficiary, who can claim it once
+//! `BountyDepositPayoutDelay` blocks have passed: the curator gets the fee and the deposit back
+//! and the beneficiary gets the rest.
+//!
+//! The curator of an active bounty can split part of its value off into child bounties, for
+//! which they propose the curators. A curator has to extend the expiry of their bounty every
+//! `BountyUpdatePeriod` blocks, or anyone can unassign them, slashing their deposit. Closing a
+//! bounty returns its funds to the room's pot, or to the parent of a child bounty.
+//!
+//! When a room is dissolved, its awarded bounties are paid out and the funds of its other
+//! bounties go to the treasury's `DissolvedPotBeneficiary`, like the rest of the room's pot. Bonds
+//! and curator deposits are returned. This is done for up to `DissolutionLimit` bounties right
+//! away and as many in every following block; no bounty can be proposed for the room meanwhile.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod mock;
+mod tests;
+
+pub use crate::pallet::*;
+use codec::{Decode, Encode};
+use frame_support::{
+	ensure,
+	traits::{
+		Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get, OnUnbalanced,
+		ReservableCurrency,
+	},
+	transactional, BoundedVec,
+};
+use frame_system::ensure_signed;
+use listen_primitives::traits::{ListenHandler, RoomTreasuryHandler};
+use pallet_treasury::RoomIndex;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, BadOrigin, Saturating, StaticLookup, Zero},
+	Permill, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+pub type BountyIndex = u32;
+
+/// A bounty of a room.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Bounty<AccountId, Balance, BlockNumber> {
+	/// The account proposing it.
+	pub proposer: AccountId,
+	/// The amount that should be paid out, curator fee included.
+	pub value: Balance,
+	/// The curator fee, included in the value.
+	pub fee: Balance,
+	/// The deposit of the curator.
+	pub curator_deposit: Balance,
+	/// The amount held on deposit (reserved) for making this proposal.
+	pub bond: Balance,
+	/// The bounty this one was split off from, if it is a child bounty.
+	pub parent: Option<BountyIndex>,
+	/// The status of this bounty.
+	pub status: BountyStatus<AccountId, BlockNumber>,
+}
+
+/// The status of a bounty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum BountyStatus<AccountId, BlockNumber> {
+	/// The bounty is proposed and waiting for approval.
+	Proposed,
+	/// The bounty is funded and waiting for a curator.
+	Funded,
+	/// A curator has been proposed and has not accepted yet.
+	CuratorProposed { curator: AccountId },
+	/// The bounty is active, its curator has to extend it before `update_due`.
+	Active { curator: AccountId, update_due: BlockNumber },
+	/// The bounty is awarded and can be claimed from `unlock_at`.
+	PendingPayout { curator: AccountId, beneficiary: AccountId, unlock_at: BlockNumber },
+}
+
+impl<AccountId, BlockNumber> BountyStatus<AccountId, BlockNumber> {
+	fn active_curator(&self) -> Option<&AccountId> {
+		match self {
+			BountyStatus::Active { curator, .. } => Some(curator),
+			_ => None,
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	pub type BalanceOf<T> = <<T as pallet_treasury::Config>::NativeCurrency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+	pub type BountyOf<T> =
+		Bounty<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_treasury::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The amount held on deposit for placing a bounty proposal.
+		#[pallet::constant]
+		type BountyDepositBase: Get<BalanceOf<Self>>;
+		/// The amount held on deposit per byte of a bounty's description.
+		#[pallet::constant]
+		type DataDepositPerByte: Get<BalanceOf<Self>>;
+		/// The delay between awarding a bounty and the beneficiary being able to claim it.
+		#[pallet::constant]
+		type BountyDepositPayoutDelay: Get<Self::BlockNumber>;
+		/// Blocks a curator has to extend their bounty in.
+		#[pallet::constant]
+		type BountyUpdatePeriod: Get<Self::BlockNumber>;
+		/// The part of the curator fee that is reserved as the curator deposit.
+		#[pallet::constant]
+		type CuratorDepositMultiplier: Get<Permill>;
+		/// Minimum value of a bounty.
+		#[pallet::constant]
+		type BountyValueMinimum: Get<BalanceOf<Self>>;
+		/// Maximum length of a bounty's description.
+		#[pallet::constant]
+		type MaximumReasonLength: Get<u32>;
+		/// Maximum number of unfinished child bounties of a bounty.
+		#[pallet::constant]
+		type MaxActiveChildBountyCount: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	#[pallet::generate_store(pub (super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// New bounty proposal. \[room_id, bounty_index\]
+		BountyProposed(RoomIndex, BountyIndex),
+		/// A bounty proposal was rejected; its bond was slashed. \[room_id, bounty_index, bond\]
+		BountyRejected(RoomIndex, BountyIndex, BalanceOf<T>),
+		/// A bounty was funded from the room's pot. \[room_id, bounty_index\]
+		BountyFunded(RoomIndex, BountyIndex),
+		/// A curator was proposed for a bounty. \[room_id, bounty_index, curator, fee\]
+		CuratorProposed(RoomIndex, BountyIndex, T::AccountId, BalanceOf<T>),
+		/// A curator accepted a bounty. \[room_id, bounty_index, curator\]
+		CuratorAccepted(RoomIndex, BountyIndex, T::AccountId),
+		/// The curator of a bounty was unassigned. \[room_id, bounty_index\]
+		CuratorUnassigned(RoomIndex, BountyIndex),
+		/// A bounty was extended. \[room_id, bounty_index\]
+		BountyExtended(RoomIndex, BountyIndex),
+		/// A bounty was awarded to a beneficiary. \[room_id, bounty_index, beneficiary\]
+		BountyAwarded(RoomIndex, BountyIndex, T::AccountId),
+		/// A bounty was claimed. \[room_id, bounty_index, payout, beneficiary\]
+		BountyClaimed(RoomIndex, BountyIndex, BalanceOf<T>, T::AccountId),
+		/// A bounty was closed and its funds returned. \[room_id, bounty_index, returned\]
+		BountyCanceled(RoomIndex, BountyIndex, BalanceOf<T>),
+		/// A child bounty was split off a bounty. \[room_id, parent_index, bounty_index\]
+		ChildBountyAdded(RoomIndex, BountyIndex, BountyIndex),
+		/// Every bounty of a dissolved room has been settled. \[room_id\]
+		RoomBountiesSettled(RoomIndex),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Proposer's balance is too low.
+		InsufficientProposersBalance,
+		/// No bounty at that index.
+		InvalidIndex,
+		/// The description is too long.
+		ReasonTooBig,
+		/// The bounty is not in a status that allows this.
+		UnexpectedStatus,
+		/// Only the curator of the bounty can do this.
+		RequireCurator,
+		/// The value is below `BountyValueMinimum`.
+		InvalidValue,
+		/// The curator fee must be lower than the value.
+		InvalidFee,
+		/// The bounty is awarded and waiting to be claimed.
+		PendingPayout,
+		/// The bounty can not be claimed or unassigned yet.
+		Premature,
+		/// The bounty has unfinished child bounties.
+		HasActiveChildBounty,
+		/// There can only be `MaxActiveChildBountyCount` unfinished child bounties.
+		TooManyChildBounties,
+		/// The bounty can not cover the child bounty and its own curator fee.
+		InsufficientBountyBalance,
+		/// The room is being dissolved.
+		RoomDissolving,
+	}
+
+	/// Number of bounties that have been proposed in a room.
+	#[pallet::storage]
+	#[pallet::getter(fn bounty_count)]
+	pub type BountyCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, BountyIndex, ValueQuery>;
+
+	/// The bounties of the rooms.
+	#[pallet::storage]
+	#[pallet::getter(fn bounties)]
+	pub type Bounties<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Twox64Concat,
+		BountyIndex,
+		BountyOf<T>,
+		OptionQuery,
+	>;
+
+	/// The description of each bounty.
+	#[pallet::storage]
+	#[pallet::getter(fn bounty_descriptions)]
+	pub type BountyDescriptions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Twox64Concat,
+		BountyIndex,
+		BoundedVec<u8, T::MaximumReasonLength>,
+		OptionQuery,
+	>;
+
+	/// Number of unfinished child bounties of a bounty.
+	#[pallet::storage]
+	#[pallet::getter(fn active_child_bounties)]
+	pub type ActiveChildBounties<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Twox64Concat,
+		BountyIndex,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Dissolved rooms whose bounties are still being settled.
+	#[pallet::storage]
+	#[pallet::getter(fn dissolving_rooms)]
+	pub type DissolvingRooms<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, (), OptionQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Settle up to `DissolutionLimit` bounties of a dissolved room.
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			let weight = T::DbWeight::get().reads(1);
+			match <DissolvingRooms<T>>::iter_keys().next() {
+				Some(room_id) => weight.saturating_add(Self::continue_dissolution(
+					room_id,
+					T::DissolutionLimit::get(),
+				)),
+				None => weight,
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Propose a bounty of `value` for a room, reserving a bond that grows with the length
+		/// of the description.
+		#[pallet::weight(1500_000_000)]
+		pub fn propose_bounty(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] value: BalanceOf<T>,
+			description: Vec<u8>,
+		) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+			Self::ensure_not_dissolving(room_id)?;
+			ensure!(value >= T::BountyValueMinimum::get(), Error::<T>::InvalidValue);
+			let bond = T::BountyDepositBase::get().saturating_add(
+				T::DataDepositPerByte::get().saturating_mul((description.len() as u32).into()),
+			);
+			let description: BoundedVec<u8, T::MaximumReasonLength> =
+				description.try_into().map_err(|_| Error::<T>::ReasonTooBig)?;
+			T::NativeCurrency::reserve(&proposer, bond)
+				.map_err(|_| Error::<T>::InsufficientProposersBalance)?;
+
+			let bounty_id = Self::insert_bounty(
+				room_id,
+				description,
+				Bounty {
+					proposer,
+					value,
+					fee: Zero::zero(),
+					curator_deposit: Zero::zero(),
+					bond,
+					parent: None,
+					status: BountyStatus::Proposed,
+				},
+			);
+
+			Self::deposit_event(Event::BountyProposed(room_id, bounty_id));
+			Ok(())
+		}
+
+		/// Fund a proposed bounty from the room's pot and return the proposer's bond. The value
+		/// counts against the room's budget.
+		#[pallet::weight(1500_000_000)]
+		#[transactional]
+		pub fn approve_bounty(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			Self::ensure_not_dissolving(room_id)?;
+
+			let mut bounty = Self::bounties(room_id, bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(bounty.status == BountyStatus::Proposed, Error::<T>::UnexpectedStatus);
+
+			pallet_treasury::Pallet::<T>::spend_from_pot(
+				room_id,
+				&Self::bounty_account_id(room_id, bounty_id),
+				bounty.value,
+			)?;
+			T::NativeCurrency::unreserve(&bounty.proposer, bounty.bond);
+
+			bounty.status = BountyStatus::Funded;
+			<Bounties<T>>::insert(room_id, bounty_id, bounty);
+
+			Self::deposit_event(Event::BountyFunded(room_id, bounty_id));
+			Ok(())
+		}
+
+		/// Propose a curator for a funded bounty. The room's `ApproveOrigin` does this for a
+		/// bounty, the parent's curator for a child bounty.
+		#[pallet::weight(1500_000_000)]
+		pub fn propose_curator(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] bounty_id: BountyIndex,
+			curator: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] fee: BalanceOf<T>,
+		) -> DispatchResult {
+			let curator = T::Lookup::lookup(curator)?;
+			let mut bounty = Self::bounties(room_id, bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			Self::ensure_bounty_manager(origin, room_id, &bounty)?;
+			ensure!(bounty.status == BountyStatus::Funded, Error::<T>::UnexpectedStatus);
+			ensure!(fee < bounty.value, Error::<T>::InvalidFee);
+
+			bounty.fee = fee;
+			bounty.status = BountyStatus::CuratorProposed { curator: curator.clone() };
+			<Bounties<T>>::insert(room_id, bounty_id, bounty);
+
+			Self::deposit_event(Event::CuratorProposed(room_id, bounty_id, curator, fee));
+			Ok(())
+		}
+
+		/// Accept the curation of a bounty, reserving the curator deposit.
+		#[pallet::weight(1500_000_000)]
+		pub fn accept_curator(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut bounty = Self::bounties(room_id, bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			match &bounty.status {
+				BountyStatus::CuratorProposed { curator } =>
+					ensure!(curator == &who, Error::<T>::RequireCurator),
+				_ => return Err(Error::<T>::UnexpectedStatus.into()),
+			}
+
+			let deposit = T::CuratorDepositMultiplier::get() * bounty.fee;
+			T::NativeCurrency::reserve(&who, deposit)?;
+			bounty.curator_deposit = deposit;
+			let update_due = Self::now().saturating_add(T::BountyUpdatePeriod::get());
+			bounty.status = BountyStatus::Active { curator: who.clone(), update_due };
+			<Bounties<T>>::insert(room_id, bounty_id, bounty);
+
+			Self::deposit_event(Event::CuratorAccepted(room_id, bounty_id, who));
+			Ok(())
+		}
+
+		/// Unassign the curator of a bounty.
+		///
+		/// A curator can step down, getting their deposit back. The room's `RejectOrigin` can
+		/// unassign any curator, and anyone can unassign a curator whose bounty has expired; an
+		/// active curator unassigned that way loses their deposit.
+		#[pallet::weight(1500_000_000)]
+		pub fn unassign_curator(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			let maybe_sender = ensure_signed(origin.clone())
+				.map(Some)
+				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
+			let mut bounty = Self::bounties(room_id, bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+
+			match &bounty.status {
+				BountyStatus::Proposed | BountyStatus::Funded =>
+					return Err(Error::<T>::UnexpectedStatus.into()),
+				BountyStatus::CuratorProposed { curator } =>
+					ensure!(maybe_sender.map_or(true, |sender| &sender == curator), BadOrigin),
+				BountyStatus::Active { curator, update_due } => match maybe_sender {
+					Some(sender) if &sender == curator => {
+						T::NativeCurrency::unreserve(curator, bounty.curator_deposit);
+					},
+					Some(_) => {
+						ensure!(*update_due < Self::now(), Error::<T>::Premature);
+						Self::slash_curator(curator, bounty.curator_deposit);
+					},
+					None => Self::slash_curator(curator, bounty.curator_deposit),
+				},
+				BountyStatus::PendingPayout { curator, .. } => {
+					ensure!(maybe_sender.is_none(), BadOrigin);
+					Self::slash_curator(curator, bounty.curator_deposit);
+				},
+			}
+
+			bounty.curator_deposit = Zero::zero();
+			bounty.status = BountyStatus::Funded;
+			<Bounties<T>>::insert(room_id, bounty_id, bounty);
+
+			Self::deposit_event(Event::CuratorUnassigned(room_id, bounty_id));
+			Ok(())
+		}
+
+		/// Extend the expiry of an active bounty by `BountyUpdatePeriod`.
+		#[pallet::weight(1500_000_000)]
+		pub fn extend_bounty_expiry(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<Bounties<T>>::try_mutate(room_id, bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+				match &mut bounty.status {
+					BountyStatus::Active { curator, update_due } => {
+						ensure!(*curator == who, Error::<T>::RequireCurator);
+						*update_due = Self::now()
+							.saturating_add(T::BountyUpdatePeriod::get())
+							.max(*update_due);
+					},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::BountyExtended(room_id, bounty_id));
+			Ok(())
+		}
+
+		/// Award an active bounty without unfinished child bounties to `beneficiary`.
+		#[pallet::weight(1500_000_000)]
+		pub fn award_bounty(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] bounty_id: BountyIndex,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			let mut bounty = Self::bounties(room_id, bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(
+				bounty.status.active_curator().ok_or(Error::<T>::UnexpectedStatus)? == &who,
+				Error::<T>::RequireCurator
+			);
+			ensure!(
+				Self::active_child_bounties(room_id, bounty_id) == 0,
+				Error::<T>::HasActiveChildBounty
+			);
+
+			let unlock_at = Self::now().saturating_add(T::BountyDepositPayoutDelay::get());
+			bounty.status = BountyStatus::PendingPayout {
+				curator: who,
+				beneficiary: beneficiary.clone(),
+				unlock_at,
+			};
+			<Bounties<T>>::insert(room_id, bounty_id, bounty);
+
+			Self::deposit_event(Event::BountyAwarded(room_id, bounty_id, beneficiary));
+			Ok(())
+		}
+
+		/// Pay out an awarded bounty once its payout delay has passed: the curator gets the fee
+		/// and their deposit back, the beneficiary gets the rest.
+		#[pallet::weight(1500_000_000)]
+		#[transactional]
+		pub fn claim_bounty(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let bounty = Self::bounties(room_id, bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			let (curator, beneficiary) = match bounty.status {
+				BountyStatus::PendingPayout { curator, beneficiary, unlock_at } => {
+					ensure!(Self::now() >= unlock_at, Error::<T>::Premature);
+					(curator, beneficiary)
+				},
+				_ => return Err(Error::<T>::UnexpectedStatus.into()),
+			};
+
+			let account = Self::bounty_account_id(room_id, bounty_id);
+			let balance = T::NativeCurrency::free_balance(&account);
+			let fee = bounty.fee.min(balance);
+			let payout = balance.saturating_sub(fee);
+			T::NativeCurrency::unreserve(&curator, bounty.curator_deposit);
+			T::NativeCurrency::transfer(&account, &curator, fee, AllowDeath)?;
+			T::NativeCurrency::transfer(&account, &beneficiary, payout, AllowDeath)?;
+			Self::remove_bounty(room_id, bounty_id, bounty.parent);
+
+			Self::deposit_event(Event::BountyClaimed(room_id, bounty_id, payout, beneficiary));
+			Ok(())
+		}
+
+		/// Close a bounty that has not been awarded.
+		///
+		/// A proposed bounty is rejected and its bond slashed. The funds of any other bounty are
+		/// returned to the room's pot, or to the parent of a child bounty, and the deposit of its
+		/// curator is returned.
+		#[pallet::weight(1500_000_000)]
+		#[transactional]
+		pub fn close_bounty(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+			// the room's pot has been swept; the dissolution settles the bounty instead
+			Self::ensure_not_dissolving(room_id)?;
+
+			let bounty = Self::bounties(room_id, bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(
+				Self::active_child_bounties(room_id, bounty_id) == 0,
+				Error::<T>::HasActiveChildBounty
+			);
+			match &bounty.status {
+				BountyStatus::Proposed => {
+					let imbalance =
+						T::NativeCurrency::slash_reserved(&bounty.proposer, bounty.bond).0;
+					T::OnSlash::on_unbalanced(imbalance);
+					Self::remove_bounty(room_id, bounty_id, None);
+					Self::deposit_event(Event::BountyRejected(room_id, bounty_id, bounty.bond));
+					return Ok(())
+				},
+				BountyStatus::Funded | BountyStatus::CuratorProposed { .. } => {},
+				BountyStatus::Active { curator, .. } => {
+					T::NativeCurrency::unreserve(curator, bounty.curator_deposit);
+				},
+				BountyStatus::PendingPayout { .. } => return Err(Error::<T>::PendingPayout.into()),
+			}
+
+			let account = Self::bounty_account_id(room_id, bounty_id);
+			let balance = T::NativeCurrency::free_balance(&account);
+			match bounty.parent {
+				None => {
+					T::NativeCurrency::transfer(
+						&account,
+						&pallet_treasury::Pallet::<T>::room_account_id(room_id),
+						balance,
+						AllowDeath,
+					)?;
+					T::ListenHandler::add_room_free_amount(
+						room_id.into(),
+						balance.saturated_into::<u128>(),
+					)?;
+				},
+				Some(parent_id) => {
+					T::NativeCurrency::transfer(
+						&account,
+						&Self::bounty_account_id(room_id, parent_id),
+						balance,
+						AllowDeath,
+					)?;
+					<Bounties<T>>::mutate(room_id, parent_id, |maybe_parent| {
+						if let Some(parent) = maybe_parent {
+							parent.value = parent.value.saturating_add(balance);
+						}
+					});
+				},
+			}
+			Self::remove_bounty(room_id, bounty_id, bounty.parent);
+
+			Self::deposit_event(Event::BountyCanceled(room_id, bounty_id, balance));
+			Ok(())
+		}
+
+		/// Split `value` off an active bounty into a funded child bounty. Only the curator of the
+		/// bounty can do this, and the bounty must still cover its own curator fee.
+		#[pallet::weight(1500_000_000)]
+		#[transactional]
+		pub fn add_child_bounty(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] parent_id: BountyIndex,
+			#[pallet::compact] value: BalanceOf<T>,
+			description: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_not_dissolving(room_id)?;
+			ensure!(value >= T::BountyValueMinimum::get(), Error::<T>::InvalidValue);
+			let description: BoundedVec<u8, T::MaximumReasonLength> =
+				description.try_into().map_err(|_| Error::<T>::ReasonTooBig)?;
+			let mut parent = Self::bounties(room_id, parent_id).ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(
+				parent.status.active_curator().ok_or(Error::<T>::UnexpectedStatus)? == &who,
+				Error::<T>::RequireCurator
+			);
+			ensure!(
+				value <= parent.value.saturating_sub(parent.fee),
+				Error::<T>::InsufficientBountyBalance
+			);
+			<ActiveChildBounties<T>>::try_mutate(room_id, parent_id, |count| -> DispatchResult {
+				ensure!(
+					*count < T::MaxActiveChildBountyCount::get(),
+					Error::<T>::TooManyChildBounties
+				);
+				*count += 1;
+				Ok(())
+			})?;
+
+			parent.value = parent.value.saturating_sub(value);
+			<Bounties<T>>::insert(room_id, parent_id, parent);
+			let bounty_id = Self::insert_bounty(
+				room_id,
+				description,
+				Bounty {
+					proposer: who,
+					value,
+					fee: Zero::zero(),
+					curator_deposit: Zero::zero(),
+					bond: Zero::zero(),
+					parent: Some(parent_id),
+					status: BountyStatus::Funded,
+				},
+			);
+			T::NativeCurrency::transfer(
+				&Self::bounty_account_id(room_id, parent_id),
+				&Self::bounty_account_id(room_id, bounty_id),
+				value,
+				AllowDeath,
+			)?;
+
+			Self::deposit_event(Event::ChildBountyAdded(room_id, parent_id, bounty_id));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn now() -> T::BlockNumber {
+			<frame_system::Pallet<T>>::block_number()
+		}
+
+		/// The account holding the funds of a bounty.
+		pub fn bounty_account_id(room_id: RoomIndex, bounty_id: BountyIndex) -> T::AccountId {
+			T::PalletId::get().into_sub_account(("bt", room_id, bounty_id))
+		}
+
+		// The room's `ApproveOrigin` manages a bounty, the parent's curator a child bounty.
+		fn ensure_bounty_manager(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			bounty: &BountyOf<T>,
+		) -> DispatchResult {
+			match bounty.parent {
+				None => {
+					T::ApproveOrigin::ensure_origin(origin)?;
+				},
+				Some(parent_id) => {
+					let who = ensure_signed(origin)?;
+					let parent =
+						Self::bounties(room_id, parent_id).ok_or(Error::<T>::InvalidIndex)?;
+					ensure!(
+						parent.status.active_curator() == Some(&who),
+						Error::<T>::RequireCurator
+					);
+				},
+			}
+			Ok(())
+		}
+
+		fn ensure_not_dissolving(room_id: RoomIndex) -> DispatchResult {
+			ensure!(
+				!<DissolvingRooms<T>>::contains_key(room_id) &&
+					!pallet_treasury::DissolvingRooms::<T>::contains_key(room_id),
+				Error::<T>::RoomDissolving
+			);
+			Ok(())
+		}
+
+		fn slash_curator(curator: &T::AccountId, deposit: BalanceOf<T>) {
+			let imbalance = T::NativeCurrency::slash_reserved(curator, deposit).0;
+			T::OnSlash::on_unbalanced(imbalance);
+		}
+
+		fn insert_bounty(
+			room_id: RoomIndex,
+			description: BoundedVec<u8, T::MaximumReasonLength>,
+			bounty: BountyOf<T>,
+		) -> BountyIndex {
+			let bounty_id = Self::bounty_count(room_id);
+			<BountyCount<T>>::insert(room_id, bounty_id + 1);
+			<Bounties<T>>::insert(room_id, bounty_id, bounty);
+			<BountyDescriptions<T>>::insert(room_id, bounty_id, description);
+			bounty_id
+		}
+
+		fn remove_bounty(room_id: RoomIndex, bounty_id: BountyIndex, parent: Option<BountyIndex>) {
+			<Bounties<T>>::remove(room_id, bounty_id);
+			<BountyDescriptions<T>>::remove(room_id, bounty_id);
+			if let Some(parent_id) = parent {
+				<ActiveChildBounties<T>>::mutate(room_id, parent_id, |count| {
+					*count = count.saturating_sub(1)
+				});
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		// Settles at most `limit` bounties of a dissolved room, and removes the room's
+		// remaining entries once they are all settled.
+		fn continue_dissolution(room_id: RoomIndex, limit: u32) -> Weight {
+			let bounties: Vec<_> =
+				<Bounties<T>>::iter_prefix(room_id).take(limit as usize).collect();
+			let count = bounties.len() as u64;
+			for (bounty_id, bounty) in bounties {
+				Self::settle_bounty(room_id, bounty_id, bounty);
+			}
+			let mut weight = T::DbWeight::get().reads_writes(2 + 3 * count, 6 * count);
+
+			// Bounties are removed as they are settled, so the next call starts from the top.
+			if <Bounties<T>>::iter_key_prefix(room_id).next().is_none() {
+				<DissolvingRooms<T>>::remove(room_id);
+				<BountyCount<T>>::remove(room_id);
+				Self::deposit_event(Event::RoomBountiesSettled(room_id));
+				weight = weight.saturating_add(T::DbWeight::get().writes(2));
+			}
+			weight
+		}
+
+		// Pays out an awarded bounty, or sends the funds of any other bounty to
+		// `DissolvedPotBeneficiary`, and returns the bond and curator deposit held for it.
+		fn settle_bounty(room_id: RoomIndex, bounty_id: BountyIndex, bounty: BountyOf<T>) {
+			<Bounties<T>>::remove(room_id, bounty_id);
+			<BountyDescriptions<T>>::remove(room_id, bounty_id);
+			<ActiveChildBounties<T>>::remove(room_id, bounty_id);
+			let account = Self::bounty_account_id(room_id, bounty_id);
+			let balance = T::NativeCurrency::free_balance(&account);
+			match bounty.status {
+				BountyStatus::Proposed => {
+					T::NativeCurrency::unreserve(&bounty.proposer, bounty.bond);
+				},
+				BountyStatus::Funded | BountyStatus::CuratorProposed { .. } => {},
+				BountyStatus::Active { curator, .. } => {
+					T::NativeCurrency::unreserve(&curator, bounty.curator_deposit);
+				},
+				BountyStatus::PendingPayout { curator, beneficiary, .. } => {
+					let fee = bounty.fee.min(balance);
+					let payout = balance.saturating_sub(fee);
+					T::NativeCurrency::unreserve(&curator, bounty.curator_deposit);
+					let _ = T::NativeCurrency::transfer(&account, &curator, fee, AllowDeath);
+					let _ = T::NativeCurrency::transfer(&account, &beneficiary, payout, AllowDeath);
+					Self::deposit_event(Event::BountyClaimed(
+						room_id,
+						bounty_id,
+						payout,
+						beneficiary,
+					));
+					return
+				},
+			}
+			if !balance.is_zero() {
+				let _ = T::NativeCurrency::transfer(
+					&account,
+					&T::DissolvedPotBeneficiary::get(),
+					balance,
+					AllowDeath,
+				);
+			}
+			Self::deposit_event(Event::BountyCanceled(room_id, bounty_id, balance));
+		}
+	}
+
+	impl<T: Config> RoomTreasuryHandler<RoomIndex> for Pallet<T> {
+		/// Settle the bounties of the room: pay out the awarded ones, send the funds of the
+		/// others to `DissolvedPotBeneficiary`, and return the bonds and curator deposits held
+		/// for them. Up to `DissolutionLimit` bounties are settled right away and as many in
+		/// every following block, until `RoomBountiesSettled` is emitted.
+		fn remove_room_treasury_info(room_id: RoomIndex) -> Weight {
+			<DissolvingRooms<T>>::insert(room_id, ());
+			T::DbWeight::get()
+				.writes(1)
+				.saturating_add(Self::continue_dissolution(room_id, T::DissolutionLimit::get()))
+		}
+	}
+}
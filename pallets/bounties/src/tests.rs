@@ -0,0 +1,210 @@
+//! Unit tests for the bounties module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use mock::{Bounties, Event, *};
+
+fn active_bounty(value: Balance, fee: Balance) -> BountyIndex {
+	fund_room(50);
+	let bounty_id = Bounties::bounty_count(ROOM);
+	assert_ok!(Bounties::propose_bounty(Origin::signed(BOB), ROOM, value, b"task".to_vec()));
+	assert_ok!(Bounties::approve_bounty(Origin::root(), ROOM, bounty_id));
+	assert_ok!(Bounties::propose_curator(Origin::root(), ROOM, bounty_id, CHARLIE, fee));
+	assert_ok!(Bounties::accept_curator(Origin::signed(CHARLIE), ROOM, bounty_id));
+	bounty_id
+}
+
+#[test]
+fn propose_and_approve_bounty_funds_it_from_the_room() {
+	ExtBuilder::build().execute_with(|| {
+		fund_room(50);
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(BOB), ROOM, 4, vec![]),
+			Error::<Runtime>::InvalidValue
+		);
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(BOB), ROOM, 10, vec![0; 9]),
+			Error::<Runtime>::ReasonTooBig
+		);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(BOB), ROOM, 10, b"task".to_vec()));
+		assert_eq!(Balances::reserved_balance(BOB), 6);
+		assert_eq!(Bounties::bounty_descriptions(ROOM, 0).unwrap().into_inner(), b"task".to_vec());
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), ROOM, 0));
+		System::assert_last_event(Event::Bounties(crate::Event::BountyFunded(ROOM, 0)));
+		assert_eq!(Bounties::bounties(ROOM, 0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(ROOM, 0)), 10);
+		assert_eq!(room_free_amount(ROOM), 40);
+		assert_noop!(
+			Bounties::approve_bounty(Origin::root(), ROOM, 0),
+			Error::<Runtime>::UnexpectedStatus
+		);
+	});
+}
+
+#[test]
+fn awarded_bounty_pays_curator_and_beneficiary() {
+	ExtBuilder::build().execute_with(|| {
+		let bounty_id = active_bounty(10, 4);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 2);
+
+		assert_noop!(
+			Bounties::award_bounty(Origin::signed(DAVE), ROOM, bounty_id, DAVE),
+			Error::<Runtime>::RequireCurator
+		);
+		assert_ok!(Bounties::award_bounty(Origin::signed(CHARLIE), ROOM, bounty_id, DAVE));
+		assert_noop!(
+			Bounties::claim_bounty(Origin::signed(DAVE), ROOM, bounty_id),
+			Error::<Runtime>::Premature
+		);
+
+		System::set_block_number(1 + BountyDepositPayoutDelay::get());
+		assert_ok!(Bounties::claim_bounty(Origin::signed(DAVE), ROOM, bounty_id));
+		System::assert_last_event(Event::Bounties(crate::Event::BountyClaimed(
+			ROOM, bounty_id, 6, DAVE,
+		)));
+		assert_eq!(Balances::free_balance(DAVE), 26);
+		assert_eq!(Balances::free_balance(CHARLIE), 24);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Bounties::bounties(ROOM, bounty_id), None);
+	});
+}
+
+#[test]
+fn expired_curator_can_be_unassigned_by_anyone() {
+	ExtBuilder::build().execute_with(|| {
+		let bounty_id = active_bounty(10, 4);
+
+		assert_noop!(
+			Bounties::unassign_curator(Origin::signed(DAVE), ROOM, bounty_id),
+			Error::<Runtime>::Premature
+		);
+		System::set_block_number(1 + BountyUpdatePeriod::get());
+		assert_ok!(Bounties::extend_bounty_expiry(Origin::signed(CHARLIE), ROOM, bounty_id));
+		System::set_block_number(2 + 2 * BountyUpdatePeriod::get());
+		assert_ok!(Bounties::unassign_curator(Origin::signed(DAVE), ROOM, bounty_id));
+
+		assert_eq!(Bounties::bounties(ROOM, bounty_id).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(CHARLIE), 18);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+	});
+}
+
+#[test]
+fn child_bounty_is_carved_from_parent_and_returned_on_close() {
+	ExtBuilder::build().execute_with(|| {
+		let parent_id = active_bounty(20, 4);
+
+		assert_noop!(
+			Bounties::add_child_bounty(Origin::signed(CHARLIE), ROOM, parent_id, 17, vec![]),
+			Error::<Runtime>::InsufficientBountyBalance
+		);
+		assert_ok!(Bounties::add_child_bounty(Origin::signed(CHARLIE), ROOM, parent_id, 6, vec![]));
+		let child_id = parent_id + 1;
+		System::assert_last_event(Event::Bounties(crate::Event::ChildBountyAdded(
+			ROOM, parent_id, child_id,
+		)));
+		assert_eq!(Bounties::bounties(ROOM, parent_id).unwrap().value, 14);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(ROOM, child_id)), 6);
+
+		assert_ok!(Bounties::propose_curator(Origin::signed(CHARLIE), ROOM, child_id, DAVE, 1));
+		assert_noop!(
+			Bounties::award_bounty(Origin::signed(CHARLIE), ROOM, parent_id, BOB),
+			Error::<Runtime>::HasActiveChildBounty
+		);
+
+		assert_ok!(Bounties::close_bounty(Origin::root(), ROOM, child_id));
+		assert_eq!(Bounties::bounties(ROOM, parent_id).unwrap().value, 20);
+		assert_eq!(Bounties::active_child_bounties(ROOM, parent_id), 0);
+
+		assert_ok!(Bounties::close_bounty(Origin::root(), ROOM, parent_id));
+		System::assert_last_event(Event::Bounties(crate::Event::BountyCanceled(
+			ROOM, parent_id, 20,
+		)));
+		assert_eq!(room_free_amount(ROOM), 50);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+	});
+}
+
+#[test]
+fn dissolution_settles_the_bounties_of_the_room() {
+	ExtBuilder::build().execute_with(|| {
+		let awarded = active_bounty(10, 4);
+		assert_ok!(Bounties::award_bounty(Origin::signed(CHARLIE), ROOM, awarded, DAVE));
+		let funded = Bounties::bounty_count(ROOM);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(BOB), ROOM, 10, b"task".to_vec()));
+		assert_ok!(Bounties::approve_bounty(Origin::root(), ROOM, funded));
+		assert_ok!(Bounties::propose_bounty(Origin::signed(BOB), ROOM, 10, b"task".to_vec()));
+		assert_eq!(room_free_amount(ROOM), 30);
+
+		// the funds of the funded bounty join what the treasury sweeps from the pot
+		<(Bounties, Treasury) as RoomTreasuryHandler<RoomIndex>>::remove_room_treasury_info(ROOM);
+		System::assert_has_event(Event::Bounties(crate::Event::BountyClaimed(
+			ROOM, awarded, 6, DAVE,
+		)));
+		System::assert_has_event(Event::Bounties(crate::Event::BountyCanceled(ROOM, funded, 10)));
+		assert_eq!(Balances::free_balance(DAVE), 26);
+		assert_eq!(Balances::free_balance(CHARLIE), 24);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(BOB), 20);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(DissolvedPotBeneficiary::get()), 40);
+		assert_eq!(room_free_amount(ROOM), 0);
+		assert_eq!(Bounties::bounty_count(ROOM), 0);
+		assert_eq!(Bounties::bounties(ROOM, funded), None);
+		assert_eq!(Bounties::bounty_descriptions(ROOM, funded), None);
+	});
+}
+
+#[test]
+fn dissolution_settles_bounties_over_several_blocks() {
+	ExtBuilder::build().execute_with(|| {
+		fund_room(50);
+		for _ in 0..5 {
+			assert_ok!(Bounties::propose_bounty(Origin::signed(ALICE), ROOM, 10, vec![]));
+		}
+		assert_eq!(Balances::reserved_balance(ALICE), 10);
+
+		<(Bounties, Treasury) as RoomTreasuryHandler<RoomIndex>>::remove_room_treasury_info(ROOM);
+		// `DissolutionLimit` is four, so one bounty waits for the next block.
+		assert_eq!(Balances::reserved_balance(ALICE), 2);
+		assert_eq!(BountyDescriptions::<Runtime>::iter_prefix(ROOM).count(), 1);
+		assert_eq!(Bounties::dissolving_rooms(ROOM), Some(()));
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(BOB), ROOM, 10, vec![]),
+			Error::<Runtime>::RoomDissolving
+		);
+
+		System::set_block_number(2);
+		Bounties::on_initialize(2);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(BountyDescriptions::<Runtime>::iter_prefix(ROOM).count(), 0);
+		assert_eq!(Bounties::dissolving_rooms(ROOM), None);
+		assert_eq!(Bounties::bounty_count(ROOM), 0);
+		System::assert_last_event(Event::Bounties(crate::Event::RoomBountiesSettled(ROOM)));
+	});
+}
+
+#[test]
+fn approved_bounties_count_against_the_room_budget() {
+	ExtBuilder::build().execute_with(|| {
+		fund_room(50);
+		pallet_treasury::RoomBudgets::<Runtime>::insert(
+			ROOM,
+			pallet_treasury::RoomBudget { max_spend: Some(15), burn: None },
+		);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(BOB), ROOM, 10, vec![]));
+		assert_ok!(Bounties::propose_bounty(Origin::signed(BOB), ROOM, 10, vec![]));
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), ROOM, 0));
+		assert_eq!(Treasury::period_spending(ROOM).1, 10);
+		assert_noop!(
+			Bounties::approve_bounty(Origin::root(), ROOM, 1),
+			pallet_treasury::Error::<Runtime>::BudgetExceeded
+		);
+		assert_eq!(room_free_amount(ROOM), 40);
+	});
+}
@@ -164,7 +164,7 @@ benchmarks_instance! {
 		let proposal_hash = T::Hashing::hash_of(&proposal);
 		// Note that execution fails due to mis-matched origin
 		assert_last_event::<T, I>(
-			RawEvent::Executed(proposal_hash, Err(DispatchError::BadOrigin)).into()
+			RawEvent::Executed(proposal_hash, Err((0, DispatchError::BadOrigin))).into()
 		);
 	}
 
@@ -445,7 +445,7 @@ benchmarks_instance! {
 	verify {
 		// The last proposal is removed.
 		assert_eq!(Collective::<T, _>::proposals().len(), (p - 1) as usize);
-		assert_last_event::<T, I>(RawEvent::Executed(last_hash, Err(DispatchError::BadOrigin)).into());
+		assert_last_event::<T, I>(RawEvent::Executed(last_hash, Err((0, DispatchError::BadOrigin))).into());
 	}
 
 	close_disapproved {
@@ -581,7 +581,7 @@ benchmarks_instance! {
 	}: close(SystemOrigin::Signed(caller), last_hash, p - 1, Weight::max_value(), bytes_in_storage)
 	verify {
 		assert_eq!(Collective::<T, _>::proposals().len(), (p - 1) as usize);
-		assert_last_event::<T, I>(RawEvent::Executed(last_hash, Err(DispatchError::BadOrigin)).into());
+		assert_last_event::<T, I>(RawEvent::Executed(last_hash, Err((0, DispatchError::BadOrigin))).into());
 	}
 
 	disapprove_proposal {
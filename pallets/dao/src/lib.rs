@@ -26,18 +26,31 @@ use frame_support::{
 		PostDispatchInfo,
 	},
 	ensure,
-	traits::{Contains, EnsureOrigin, Get},
-	weights::GetDispatchInfo,
+	traits::{Contains, EnsureOrigin, Get, PalletInfoAccess},
+	transactional,
+	weights::{GetDispatchInfo, Weight},
+	BoundedVec, CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use frame_system::{self as system, ensure_root, ensure_signed};
-use listen_primitives::traits::{CollectiveHandler, ListenHandler};
+use listen_primitives::traits::{
+	CollectiveHandler, EscalationHandler, GovernanceObserver, ListenHandler, MotionOutcome,
+	PendingSpends, VoteWeightSource,
+};
 use scale_info::TypeInfo;
-use sp_runtime::{traits::Hash, RuntimeDebug};
+use sp_io::MultiRemovalResults;
+use sp_runtime::{
+	traits::{CheckedDiv, Hash, IdentifyAccount, IntegerSquareRoot, One, Verify, Zero},
+	Permill, RuntimeDebug, SaturatedConversion,
+};
 use sp_std::{convert::From, prelude::*, result};
 pub use weights::WeightInfo;
 
+pub mod migrations;
 pub mod weights;
 
+mod mock;
+mod tests;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
@@ -52,6 +65,9 @@ pub type MemberCount = u32;
 pub type IsEnd = bool;
 pub type IsPass = bool;
 
+/// Domain separator of the payload members sign for `submit_signed_votes`.
+pub const SIGNED_VOTE_CONTEXT: &[u8] = b"listen/dao/signed-vote";
+
 /// Default voting strategy when a member is inactive.
 pub trait DefaultVote {
 	/// Get the default voting strategy, given:
@@ -107,6 +123,12 @@ pub enum RoomRawOrigin<AccountId, I> {
 	Members(MemberCount, MemberCount),
 	/// It has been condoned by a single member of the collective.
 	Member(RoomIndex, AccountId),
+	/// It has been condoned by a given number of members of the council of a room from a given
+	/// total.
+	RoomMembers(RoomIndex, MemberCount, MemberCount),
+	/// It has been condoned by a majority of the council of all the given rooms, each with the
+	/// number of members that approved it and the size of the council.
+	Rooms(Vec<(RoomIndex, MemberCount, MemberCount)>),
 	/// Dummy to manage the fact we have instancing.
 	_Phantom(sp_std::marker::PhantomData<I>),
 }
@@ -116,32 +138,160 @@ pub enum RoomRawOrigin<AccountId, I> {
 pub struct ListenDaoVotes<AccountId, BlockNumber> {
 	/// The proposal's unique index.
 	index: ProposalIndex,
-	/// The proposal's reason,
-	reason: Option<Vec<u8>>,
 	/// The number of approval ListenDaoVotes that are needed to pass the motion.
 	threshold: MemberCount,
 	/// The current set of voters that approved it.
 	ayes: Vec<AccountId>,
 	/// The current set of voters that rejected it.
 	nays: Vec<AccountId>,
+	/// The current set of voters that abstained. They count as having voted, but neither for
+	/// nor against the motion.
+	abstentions: Vec<AccountId>,
 	/// The hard end time of this vote.
 	end: BlockNumber,
 }
 
+/// A vote on a motion.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum Ballot {
+	Aye,
+	Nay,
+	/// Take part without voting for or against the motion. Abstaining members are not absent,
+	/// so `DefaultVote` never applies to them.
+	Abstain,
+}
+
+impl From<bool> for Ballot {
+	fn from(approve: bool) -> Self {
+		if approve {
+			Ballot::Aye
+		} else {
+			Ballot::Nay
+		}
+	}
+}
+
+/// Description of a motion, which its proposer can amend while it is open.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	DefaultNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(T, I))]
+pub struct MotionMetadata<T: Config<I>, I: 'static = ()> {
+	/// A short title.
+	pub title: BoundedVec<u8, T::MaxTitleLen>,
+	/// The hash of a longer description kept off-chain.
+	pub description_hash: Option<T::Hash>,
+	/// An external link or IPFS CID.
+	pub link: BoundedVec<u8, T::MaxLinkLen>,
+}
+
+/// How the members of a room vote on its motions.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum VotingMode {
+	/// Every member casts one vote per motion.
+	OneMemberOneVote,
+	/// Every member spends `n²` voice credits to cast `n` votes on a motion. The threshold of
+	/// a motion then counts votes, not members.
+	Quadratic,
+	/// Every member casts one vote per motion, weighing their `VoteWeight` in the room. The
+	/// threshold of a motion then counts weight, not members.
+	Weighted,
+}
+
+impl Default for VotingMode {
+	fn default() -> Self {
+		VotingMode::OneMemberOneVote
+	}
+}
+
+/// How the calls of a batch motion are dispatched.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum BatchMode {
+	/// Every call must succeed, otherwise the effects of all calls are reverted.
+	AllOrNothing,
+	/// Every call is dispatched even if an earlier one failed.
+	BestEffort,
+}
+
+/// The body of a motion.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum RoomMotion<Proposal> {
+	/// A single call.
+	Single(Proposal),
+	/// A list of calls dispatched one after another under the same origin.
+	Batch(Vec<Proposal>, BatchMode),
+}
+
+impl<Proposal> RoomMotion<Proposal> {
+	/// All calls of the motion.
+	pub fn calls(&self) -> &[Proposal] {
+		match self {
+			RoomMotion::Single(proposal) => sp_std::slice::from_ref(proposal),
+			RoomMotion::Batch(calls, _) => &calls[..],
+		}
+	}
+}
+
+/// A motion that only executes once the council of every participating room approved it.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct CrossRoomMotion<AccountId, Proposal, BlockNumber> {
+	/// The account that proposed the motion.
+	pub proposer: AccountId,
+	/// The call dispatched once every room approved it.
+	pub proposal: Proposal,
+	/// The participating rooms.
+	pub rooms: Vec<RoomIndex>,
+	/// The rooms whose council reached its threshold, with the number of members that approved
+	/// it and the size of the council at the time.
+	pub approved: Vec<(RoomIndex, MemberCount, MemberCount)>,
+	/// The hard end time of the motion.
+	pub end: BlockNumber,
+}
+
+/// A call that is proposed again in its room every `period` blocks.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct RecurringMotion<Proposal, BlockNumber> {
+	/// The call of every motion.
+	pub proposal: Proposal,
+	/// The number of approval votes every motion needs.
+	pub threshold: MemberCount,
+	/// Number of blocks between two motions.
+	pub period: BlockNumber,
+	/// Number of motions still to be proposed.
+	pub remaining: u32,
+	/// The block at which the next motion is proposed.
+	pub next: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::pallet_prelude::{
-		Blake2_128Concat, IsType, OptionQuery, PhantomData, StorageDoubleMap, StorageMap,
-		ValueQuery,
+	use frame_support::{
+		pallet_prelude::{
+			Blake2_128Concat, Hooks, IsType, OptionQuery, PhantomData, StorageDoubleMap,
+			StorageMap, StorageValue, ValueQuery,
+		},
+		traits::StorageVersion,
 	};
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
 	#[pallet::config]
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
 		/// The outer origin type.
-		type Origin: From<RoomRawOrigin<Self::AccountId, I>>;
+		type Origin: From<RoomRawOrigin<Self::AccountId, I>>
+			+ Into<Result<RoomRawOrigin<Self::AccountId, I>, <Self as Config<I>>::Origin>>
+			+ IsType<<Self as frame_system::Config>::Origin>;
 		/// The outer call dispatch type.
 		type Proposal: Parameter
 			+ Dispatchable<Origin = <Self as Config<I>>::Origin, PostInfo = PostDispatchInfo>
@@ -162,11 +312,76 @@ pub mod pallet {
 		/// Maximum number of proposals allowed to be active in parallel.
 		#[pallet::constant]
 		type MaxProposals: Get<ProposalIndex>;
+		/// Fraction of the room council that must have voted aye before the room root or prime
+		/// can fast-track a motion.
+		#[pallet::constant]
+		type FastTrackThreshold: Get<Permill>;
+		/// Number of blocks during which a vetoed proposal hash can not be proposed again.
+		#[pallet::constant]
+		type VetoCoolOff: Get<Self::BlockNumber>;
+		/// Signature members use to sign their ballots off-chain.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+		/// Public key that verifies an `OffchainSignature`.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+		/// Maximum number of signed ballots that can be submitted in one call.
+		#[pallet::constant]
+		type MaxSignedVotes: Get<u32>;
+		/// Calls that spend the room's shared funds. Approved motions of these calls only
+		/// execute after `GracePeriod`.
+		type SpendCalls: Contains<Self::Proposal>;
+		/// Number of blocks between the approval of a spend motion and its execution, during
+		/// which members who voted nay can rage-quit.
+		#[pallet::constant]
+		type GracePeriod: Get<Self::BlockNumber>;
+		/// Maximum number of spend motions executed per block once their grace period is over.
+		/// The others execute in the following blocks.
+		#[pallet::constant]
+		type MaxGraceExecutions: Get<u32>;
+		/// The approved spends of a room that have not been paid yet. Members who rage-quit get
+		/// no share of them.
+		type PendingSpends: PendingSpends<RoomIndex, u128>;
+		/// Maximum number of calls in a batch motion.
+		#[pallet::constant]
+		type MaxBatchCalls: Get<u32>;
+		/// Voice credits a member of a quadratic voting room can spend every `MotionDuration`.
+		#[pallet::constant]
+		type VoiceCredits: Get<u32>;
+		/// The collective that approved escalated motions are forwarded to, usually another
+		/// instance of this pallet.
+		type Escalation: EscalationHandler<
+			RoomIndex,
+			RoomMotion<<Self as Config<I>>::Proposal>,
+			DispatchError,
+		>;
+		/// Maximum length of the title of a motion.
+		#[pallet::constant]
+		type MaxTitleLen: Get<u32>;
+		/// Maximum length of the link of a motion.
+		#[pallet::constant]
+		type MaxLinkLen: Get<u32>;
+		/// Maximum length of the rationale attached to a vote.
+		#[pallet::constant]
+		type MaxRationaleLen: Get<u32>;
+		/// Notified of the proposer and the voters of every motion that closes.
+		type Observer: GovernanceObserver<RoomIndex, Self::AccountId>;
+		/// The weight of the votes of members in rooms with `VotingMode::Weighted`.
+		type VoteWeight: VoteWeightSource<RoomIndex, Self::AccountId>;
+		/// Maximum number of rooms taking part in a cross-room motion.
+		#[pallet::constant]
+		type MaxCrossRooms: Get<u32>;
+		/// Maximum number of recurring motions registered in a room.
+		#[pallet::constant]
+		type MaxRecurringMotions: Get<u32>;
+		/// Maximum number of storage items removed per block while a dissolved room is cleaned
+		/// up.
+		#[pallet::constant]
+		type DissolutionLimit: Get<u32>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	#[pallet::generate_store(pub (super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::event]
@@ -177,34 +392,114 @@ pub mod pallet {
 		/// \[account, proposal_index, proposal_hash, threshold\]
 		Proposed(T::AccountId, ProposalIndex, T::Hash, MemberCount),
 		/// A motion (given hash) has been voted on by given account, leaving
-		/// a tally (yes votes and no votes given respectively as `MemberCount`).
-		/// \[account, proposal_hash, voted, yes, no\]
-		Voted(T::AccountId, T::Hash, bool, MemberCount, MemberCount, MemberCount),
+		/// a tally (yes, no and abstain votes given respectively as `MemberCount`), with the
+		/// hash of the voter's rationale if any.
+		/// \[account, proposal_hash, ballot, seats, yes, no, abstain, rationale_hash\]
+		Voted(
+			T::AccountId,
+			T::Hash,
+			Ballot,
+			MemberCount,
+			MemberCount,
+			MemberCount,
+			MemberCount,
+			Option<T::Hash>,
+		),
+		/// A relayed ballot repeated the member's current vote and was skipped.
+		/// \[account, proposal_hash\]
+		SignedVoteSkipped(T::AccountId, T::Hash),
 		/// A motion was approved by the required threshold.
 		/// \[proposal_hash\]
 		Approved(T::Hash),
 		/// A motion was not approved by the required threshold.
 		/// \[proposal_hash\]
 		Disapproved(T::Hash),
-		/// A motion was executed; result will be `Ok` if it returned without error.
+		/// A motion was executed; result will be `Ok` if every call returned without error, or
+		/// `Err` with the index and error of the first failing call.
 		/// \[proposal_hash, result\]
-		Executed(T::Hash, DispatchResult),
+		Executed(T::Hash, Result<(), (u32, DispatchError)>),
 		/// A single member did some action; result will be `Ok` if it returned without error.
 		/// \[proposal_hash, result\]
 		MemberExecuted(T::Hash, DispatchResult),
 		/// A proposal was closed because its threshold was reached or after its duration was up.
 		/// \[proposal_hash, yes, no\]
 		Closed(T::Hash, MemberCount, MemberCount),
+		/// A motion was approved early by the room root or prime with a supermajority of ayes.
+		/// \[who, proposal_hash, yes\]
+		FastTracked(T::AccountId, T::Hash, MemberCount),
+		/// A motion was vetoed by the room prime and can not be proposed again before the given
+		/// block.
+		/// \[who, proposal_hash, until\]
+		Vetoed(T::AccountId, T::Hash, T::BlockNumber),
+		/// An approved spend motion entered its grace period and will execute at the given block.
+		/// \[proposal_hash, execute_at\]
+		GracePeriodStarted(T::Hash, T::BlockNumber),
+		/// A dissenting member left the room council with a share of the room's free amount.
+		/// \[who, room_id, payout\]
+		RageQuit(T::AccountId, RoomIndex, u128),
+		/// The room root changed how the room votes.
+		/// \[room_id, mode\]
+		VotingModeSet(RoomIndex, VotingMode),
+		/// A member of a quadratic voting room cast votes on a motion, leaving a tally of the
+		/// summed yes and no votes.
+		/// \[account, proposal_hash, voted, votes, yes, no\]
+		QuadraticVoted(T::AccountId, T::Hash, bool, u32, u32, u32),
+		/// A motion will be forwarded to the escalation collective with a threshold once it is
+		/// approved here.
+		/// \[who, room_id, proposal_index, threshold\]
+		EscalationRequested(T::AccountId, RoomIndex, ProposalIndex, MemberCount),
+		/// An approved motion was forwarded to the escalation collective.
+		/// \[room_id, proposal_index, proposal_hash, escalated_index\]
+		Escalated(RoomIndex, ProposalIndex, T::Hash, ProposalIndex),
+		/// The escalation collective refused an approved motion, which stays open here.
+		/// \[room_id, proposal_index, proposal_hash, error\]
+		EscalationFailed(RoomIndex, ProposalIndex, T::Hash, DispatchError),
+		/// A motion was forwarded from another collective, where it had `source_index`.
+		/// \[room_id, proposal_index, proposal_hash, source_index\]
+		ProposedByEscalation(RoomIndex, ProposalIndex, T::Hash, ProposalIndex),
+		/// The proposer amended the metadata of an open motion, resetting its votes if asked to.
+		/// \[who, room_id, proposal_index, metadata, votes_reset\]
+		Amended(T::AccountId, RoomIndex, ProposalIndex, MotionMetadata<T, I>, bool),
+		/// A motion needing the approval of several rooms has been proposed.
+		/// \[account, cross_room_index, proposal_hash, rooms\]
+		CrossRoomProposed(T::AccountId, ProposalIndex, T::Hash, Vec<RoomIndex>),
+		/// A member voted on a cross-room motion for their room, leaving the room's tally.
+		/// \[account, cross_room_index, room_id, ballot, yes, no\]
+		CrossRoomVoted(T::AccountId, ProposalIndex, RoomIndex, Ballot, MemberCount, MemberCount),
+		/// The council of a room reached its threshold on a cross-room motion.
+		/// \[cross_room_index, room_id\]
+		CrossRoomApproved(ProposalIndex, RoomIndex),
+		/// A cross-room motion was rejected, either because the given room can no longer reach
+		/// its threshold or, without a room, because it expired.
+		/// \[cross_room_index, room_id\]
+		CrossRoomRejected(ProposalIndex, Option<RoomIndex>),
+		/// A cross-room motion approved by every room was executed; result will be `Ok` if it
+		/// returned without error.
+		/// \[cross_room_index, result\]
+		CrossRoomExecuted(ProposalIndex, DispatchResult),
+		/// A recurring motion was registered in a room.
+		/// \[room_id, recurring_index, proposal_hash\]
+		RecurringRegistered(RoomIndex, u32, T::Hash),
+		/// A recurring motion was cancelled.
+		/// \[room_id, recurring_index\]
+		RecurringCancelled(RoomIndex, u32),
+		/// A recurring motion was due; result will be `Ok` with the index of the new motion.
+		/// \[room_id, recurring_index, result\]
+		RecurringProposed(RoomIndex, u32, Result<ProposalIndex, DispatchError>),
+		/// Every motion and vote of a dissolved room has been removed.
+		/// \[room_id\]
+		RoomDissolved(RoomIndex),
 	}
 
 	/// Origin for the collective pallet.
 	#[pallet::origin]
 	pub type Origin<T, I = ()> = RoomRawOrigin<<T as frame_system::Config>::AccountId, I>;
 
+	/// Indexes of the motions of a room that are open for voting.
 	#[pallet::storage]
 	#[pallet::getter(fn proposals)]
 	pub type Proposals<T: Config<I>, I: 'static = ()> =
-		StorageMap<_, Blake2_128Concat, RoomIndex, Vec<T::Hash>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, RoomIndex, Vec<ProposalIndex>, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn proposal_of)]
@@ -213,11 +508,24 @@ pub mod pallet {
 		Blake2_128Concat,
 		RoomIndex,
 		Blake2_128Concat,
-		T::Hash,
-		T::Proposal,
+		ProposalIndex,
+		RoomMotion<T::Proposal>,
 		OptionQuery,
 	>;
 
+	/// Indexes of the motions of a room that carry a given proposal hash.
+	#[pallet::storage]
+	#[pallet::getter(fn proposals_by_hash)]
+	pub type ProposalsByHash<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		T::Hash,
+		Vec<ProposalIndex>,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn voting)]
 	pub type Voting<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
@@ -225,15 +533,228 @@ pub mod pallet {
 		Blake2_128Concat,
 		RoomIndex,
 		Blake2_128Concat,
-		T::Hash,
+		ProposalIndex,
 		ListenDaoVotes<T::AccountId, T::BlockNumber>,
 	>;
 
+	/// Number of motions that have been proposed in a room. This is the index of the next
+	/// motion.
 	#[pallet::storage]
 	#[pallet::getter(fn proposal_count)]
 	pub type ProposalCount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, ProposalIndex, ValueQuery>;
+
+	/// Vetoed proposal hashes of a room, with the block until which they can not be proposed
+	/// again and the accounts that have already vetoed them.
+	#[pallet::storage]
+	#[pallet::getter(fn blacklist)]
+	pub type Blacklist<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		T::Hash,
+		(T::BlockNumber, Vec<T::AccountId>),
+		OptionQuery,
+	>;
+
+	/// Nonce of the next signed ballot of a member in a room.
+	#[pallet::storage]
+	#[pallet::getter(fn signed_vote_nonce)]
+	pub type SignedVoteNonce<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		u64,
+		ValueQuery,
+	>;
+
+	/// Approved spend motions waiting for the end of their grace period, with their final votes
+	/// and the block at which they execute.
+	#[pallet::storage]
+	#[pallet::getter(fn grace_periods)]
+	pub type GracePeriods<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		(ListenDaoVotes<T::AccountId, T::BlockNumber>, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	/// Spend motions to execute at a given block.
+	#[pallet::storage]
+	#[pallet::getter(fn grace_queue)]
+	pub type GraceQueue<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		Vec<(RoomIndex, ProposalIndex)>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn voting_mode)]
+	pub type RoomVotingMode<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, VotingMode, ValueQuery>;
+
+	/// Summed yes and no votes of the open motions of quadratic voting rooms.
+	#[pallet::storage]
+	#[pallet::getter(fn quadratic_tally)]
+	pub type QuadraticTally<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		(u32, u32),
+		ValueQuery,
+	>;
+
+	/// Voice credits a member has spent in a room, with the period they were spent in.
+	#[pallet::storage]
+	#[pallet::getter(fn spent_voice_credits)]
+	pub type SpentVoiceCredits<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		(T::BlockNumber, u32),
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_of)]
+	pub type MetadataOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		MotionMetadata<T, I>,
+		OptionQuery,
+	>;
+
+	/// The account that proposed a motion and can amend its metadata.
+	#[pallet::storage]
+	#[pallet::getter(fn proposer_of)]
+	pub type ProposerOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	/// Open motions that are forwarded to the escalation collective once approved, with the
+	/// account that escalated them and the threshold to use there.
+	#[pallet::storage]
+	#[pallet::getter(fn escalations)]
+	pub type Escalations<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		(T::AccountId, MemberCount),
+		OptionQuery,
+	>;
+
+	/// The index in the escalating collective of motions that were forwarded from there. These
+	/// motions have no proposer.
+	#[pallet::storage]
+	#[pallet::getter(fn escalated_from)]
+	pub type EscalatedFrom<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		ProposalIndex,
+		OptionQuery,
+	>;
+
+	/// Number of cross-room motions that have been proposed. This is the index of the next one.
+	#[pallet::storage]
+	#[pallet::getter(fn cross_room_count)]
+	pub type CrossRoomCount<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, ProposalIndex, ValueQuery>;
+
+	/// Open cross-room motions.
+	#[pallet::storage]
+	#[pallet::getter(fn cross_room_motions)]
+	pub type CrossRoomMotions<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ProposalIndex,
+		CrossRoomMotion<T::AccountId, T::Proposal, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The separate tally of every room on an open cross-room motion.
+	#[pallet::storage]
+	#[pallet::getter(fn cross_room_voting)]
+	pub type CrossRoomVoting<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ProposalIndex,
+		Blake2_128Concat,
+		RoomIndex,
+		ListenDaoVotes<T::AccountId, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The open cross-room motions every room takes part in.
+	#[pallet::storage]
+	#[pallet::getter(fn cross_rooms_of)]
+	pub type CrossRoomsOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		(),
+		OptionQuery,
+	>;
+
+	/// Number of recurring motions that have been registered in a room. This is the index of the
+	/// next one.
+	#[pallet::storage]
+	#[pallet::getter(fn recurring_count)]
+	pub type RecurringCount<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, RoomIndex, u32, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn recurring_motions)]
+	pub type RecurringMotions<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		u32,
+		RecurringMotion<T::Proposal, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// Recurring motions to propose at a given block.
+	#[pallet::storage]
+	#[pallet::getter(fn recurring_queue)]
+	pub type RecurringQueue<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<(RoomIndex, u32)>, ValueQuery>;
+
+	/// Dissolved rooms whose storage is still being removed, with the index of the map being
+	/// cleared and the cursor to continue from.
+	#[pallet::storage]
+	#[pallet::getter(fn dissolving_rooms)]
+	pub type DissolvingRooms<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, (u8, Option<Vec<u8>>), OptionQuery>;
+
 	#[pallet::error]
 	pub enum Error<T, I = ()> {
 		/// Account is not a member
@@ -253,6 +774,111 @@ pub mod pallet {
 		WrongProposalLength,
 		VoteExpire,
 		DisallowFunc,
+		/// Account is neither the room root nor the room prime
+		NotRootOrPrime,
+		/// Account is not the room prime
+		NotPrime,
+		/// Not enough ayes to fast-track the motion
+		NotSupermajority,
+		/// The proposal was vetoed and is still cooling off
+		ProposalBlacklisted,
+		/// The prime has already vetoed this proposal
+		AlreadyVetoed,
+		/// The ballot signature does not match the member and nonce
+		InvalidSignature,
+		/// Account did not vote nay on any motion in its grace period
+		NotDissenter,
+		/// A batch motion must have between one and `MaxBatchCalls` calls
+		WrongBatchSize,
+		/// The room does not vote this way
+		WrongVotingMode,
+		/// The voting mode can not change while motions are open
+		MotionsOpen,
+		/// Not enough voice credits left in this period
+		InsufficientVoiceCredits,
+		/// A quadratic ballot must cast at least one vote
+		ZeroVotes,
+		/// The motion is already escalated
+		AlreadyEscalated,
+		/// Account did not propose the motion
+		NotProposer,
+		/// A cross-room motion must have between two and `MaxCrossRooms` rooms
+		WrongRoomCount,
+		/// A room is listed more than once
+		DuplicateRoom,
+		/// Cross-room motion must exist
+		CrossRoomMissing,
+		/// The room does not take part in the cross-room motion
+		NotParticipatingRoom,
+		/// The room has already approved the cross-room motion
+		RoomAlreadyApproved,
+		/// The cross-room motion is still open for voting
+		VoteNotExpired,
+		/// A recurring motion needs a non-zero period and at least one repetition
+		WrongRecurrence,
+		/// A recurring motion needs the votes of at least two members and of a majority of the
+		/// room council
+		RecurringThresholdTooLow,
+		/// There can only be `MaxRecurringMotions` recurring motions in a room
+		TooManyRecurringMotions,
+		/// Recurring motion must exist
+		RecurringMissing,
+		/// The room is being dissolved
+		RoomDissolving,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let mut due = <GraceQueue<T, I>>::take(n);
+			let limit = T::MaxGraceExecutions::get() as usize;
+			if due.len() > limit {
+				// The rest go first in the next block.
+				let mut rest = due.split_off(limit);
+				<GraceQueue<T, I>>::mutate(n + One::one(), |next| {
+					rest.append(next);
+					*next = rest;
+				});
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			}
+			for (room_id, index) in due {
+				weight = weight.saturating_add(Self::end_grace_period(room_id, index));
+			}
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			for (room_id, id) in <RecurringQueue<T, I>>::take(n) {
+				weight = weight.saturating_add(Self::propose_recurring(room_id, id));
+			}
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			if let Some(room_id) = <DissolvingRooms<T, I>>::iter_keys().next() {
+				weight = weight.saturating_add(Self::continue_dissolution(
+					room_id,
+					T::DissolutionLimit::get(),
+				));
+			}
+			weight
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T, I>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			// Storage of older versions does not decode with the current types, so it is only
+			// checked once migrated.
+			if <Self as frame_support::traits::GetStorageVersion>::on_chain_storage_version() <
+				STORAGE_VERSION
+			{
+				return Ok(())
+			}
+			Self::do_try_state()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
 	}
 
 	#[pallet::call]
@@ -296,7 +922,7 @@ pub mod pallet {
 			room_id: RoomIndex,
 			#[pallet::compact] threshold: MemberCount,
 			proposal: Box<<T as Config<I>>::Proposal>,
-			reason: Option<Vec<u8>>,
+			metadata: MotionMetadata<T, I>,
 			#[pallet::compact] length_bound: u32,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
@@ -304,138 +930,937 @@ pub mod pallet {
 				<T as pallet::Config<I>>::BaseCallFilter::contains(&proposal),
 				Error::<T, I>::DisallowFunc
 			);
-			let members = T::ListenHandler::get_room_council(room_id.into())?;
-			ensure!(members.contains(&who), Error::<T, I>::NotMember);
 
 			let proposal_len = proposal.using_encoded(|x| x.len());
 			ensure!(proposal_len <= length_bound as usize, Error::<T, I>::WrongProposalLength);
 
-			let proposal_hash = T::Hashing::hash_of(&proposal);
+			Self::do_propose(who, room_id, threshold, RoomMotion::Single(*proposal), metadata)
+		}
+
+		/// Introduce a motion whose calls are all dispatched under the same origin once it is
+		/// approved.
+		///
+		/// With `BatchMode::AllOrNothing` a failing call reverts the whole batch, with
+		/// `BatchMode::BestEffort` the remaining calls are still dispatched.
+		#[pallet::weight(1500_000_000)]
+		pub fn propose_batch(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] threshold: MemberCount,
+			calls: Vec<<T as Config<I>>::Proposal>,
+			mode: BatchMode,
+			metadata: MotionMetadata<T, I>,
+			#[pallet::compact] length_bound: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				!calls.is_empty() && calls.len() <= T::MaxBatchCalls::get() as usize,
+				Error::<T, I>::WrongBatchSize
+			);
 			ensure!(
-				!<ProposalOf<T, I>>::contains_key(room_id, proposal_hash),
-				Error::<T, I>::DuplicateProposal
+				calls
+					.iter()
+					.all(|call| <T as pallet::Config<I>>::BaseCallFilter::contains(call)),
+				Error::<T, I>::DisallowFunc
 			);
 
-			if threshold < 2 {
-				let seats = members.len() as MemberCount;
-				let result = proposal.dispatch(RoomRawOrigin::Members(1, seats).into());
-				Self::deposit_event(Event::Executed(
-					proposal_hash,
-					result.map(|_| ()).map_err(|e| e.error),
-				));
-				Ok(())
-			} else {
-				let _active_proposals = <Proposals<T, I>>::try_mutate(
-					room_id,
-					|proposals| -> Result<usize, DispatchError> {
-						proposals.push(proposal_hash);
-						ensure!(
-							proposals.len() <= T::MaxProposals::get() as usize,
-							Error::<T, I>::TooManyProposals
-						);
-						Ok(proposals.len())
-					},
-				)?;
-				let index = Self::proposal_count(room_id);
-				ProposalCount::<T, I>::mutate(room_id, |i| *i += 1);
-				<ProposalOf<T, I>>::insert(room_id, proposal_hash, *proposal);
-				let end = system::Pallet::<T>::block_number() + T::MotionDuration::get();
-				let votes = ListenDaoVotes {
-					index,
-					reason,
-					threshold,
-					ayes: vec![who.clone()],
-					nays: vec![],
-					end,
-				};
-				<Voting<T, I>>::insert(room_id, proposal_hash, votes);
+			let motion = RoomMotion::Batch(calls, mode);
+			let motion_len = motion.using_encoded(|x| x.len());
+			ensure!(motion_len <= length_bound as usize, Error::<T, I>::WrongProposalLength);
 
-				Self::deposit_event(Event::Proposed(who, index, proposal_hash, threshold));
-				Ok(())
-			}
+			Self::do_propose(who, room_id, threshold, motion, metadata)
 		}
 
+		/// Vote aye, nay or abstain on a motion, optionally with a rationale. Only the hash of
+		/// the rationale is kept, in the `Voted` event.
 		#[pallet::weight(1500_000_000)]
 		pub fn vote(
 			origin: OriginFor<T>,
 			room_id: RoomIndex,
 			proposal: T::Hash,
 			#[pallet::compact] index: ProposalIndex,
-			approve: bool,
+			ballot: Ballot,
+			rationale: Option<BoundedVec<u8, T::MaxRationaleLen>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			let members = T::ListenHandler::get_room_council(room_id.into())?;
 			let seats = members.len() as MemberCount;
 			ensure!(members.contains(&who), Error::<T, I>::NotMember);
+			Self::ensure_proposal_hash(room_id, index, &proposal)?;
 
-			let mut voting =
-				Self::voting(room_id, &proposal).ok_or(Error::<T, I>::ProposalMissing)?;
-			ensure!(voting.index == index, Error::<T, I>::WrongIndex);
-
-			let position_yes = voting.ayes.iter().position(|a| a == &who);
-			let position_no = voting.nays.iter().position(|a| a == &who);
+			let rationale_hash = rationale.map(|r| T::Hashing::hash(&r[..]));
+			Self::do_vote(who, room_id, proposal, index, ballot, rationale_hash, seats)
+		}
 
-			if approve {
-				if position_yes.is_none() {
-					voting.ayes.push(who.clone());
-				} else {
-					Err(Error::<T, I>::DuplicateVote)?
-				}
-				if let Some(pos) = position_no {
-					voting.nays.swap_remove(pos);
-				}
-			} else {
-				if position_no.is_none() {
-					voting.nays.push(who.clone());
-				} else {
-					Err(Error::<T, I>::DuplicateVote)?
-				}
-				if let Some(pos) = position_yes {
-					voting.ayes.swap_remove(pos);
-				}
-			}
+		/// Vote on a motion given only its index.
+		#[pallet::weight(1500_000_000)]
+		pub fn vote_by_index(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] index: ProposalIndex,
+			ballot: Ballot,
+			rationale: Option<BoundedVec<u8, T::MaxRationaleLen>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let members = T::ListenHandler::get_room_council(room_id.into())?;
+			let seats = members.len() as MemberCount;
+			ensure!(members.contains(&who), Error::<T, I>::NotMember);
 
-			let yes_votes = voting.ayes.len() as MemberCount;
-			let no_votes = voting.nays.len() as MemberCount;
-			Self::deposit_event(Event::Voted(who, proposal, approve, seats, yes_votes, no_votes));
-			Voting::<T, I>::insert(room_id, &proposal, voting.clone());
+			let motion = Self::proposal_of(room_id, index).ok_or(Error::<T, I>::ProposalMissing)?;
+			let proposal = Self::motion_hash(&motion);
 
-			Self::normal_close(voting.clone(), room_id, proposal)?;
-			Ok(())
+			let rationale_hash = rationale.map(|r| T::Hashing::hash(&r[..]));
+			Self::do_vote(who, room_id, proposal, index, ballot, rationale_hash, seats)
 		}
 
-		#[pallet::weight(1500_000_000)]
-		pub fn disapprove_proposal(
+		/// Apply ballots that members signed off-chain, so that they do not pay the fee.
+		///
+		/// Each member signs `SIGNED_VOTE_CONTEXT`, the pallet name, the genesis hash, the room,
+		/// the proposal hash and index, the vote and the member's current `SignedVoteNonce`.
+		/// Any account can relay the ballots. A ballot that repeats the member's current vote is
+		/// skipped with `SignedVoteSkipped`.
+		#[pallet::weight(1500_000_000u64.saturating_mul(1 + votes.len() as u64))]
+		#[transactional]
+		pub fn submit_signed_votes(
 			origin: OriginFor<T>,
 			room_id: RoomIndex,
-			proposal_hash: T::Hash,
+			proposal: T::Hash,
+			#[pallet::compact] index: ProposalIndex,
+			votes: BoundedVec<(T::AccountId, bool, T::OffchainSignature), T::MaxSignedVotes>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let members = T::ListenHandler::get_room_council(room_id.into())?;
+			let seats = members.len() as MemberCount;
+			Self::ensure_proposal_hash(room_id, index, &proposal)?;
+
+			for (who, approve, signature) in votes.iter() {
+				ensure!(members.contains(who), Error::<T, I>::NotMember);
+				let nonce = Self::signed_vote_nonce(room_id, who);
+				let payload = Self::signed_vote_payload(room_id, &proposal, index, *approve, nonce);
+				ensure!(signature.verify(&payload[..], who), Error::<T, I>::InvalidSignature);
+				<SignedVoteNonce<T, I>>::insert(room_id, who, nonce + 1);
+			}
+
+			for (who, approve, _) in votes {
+				// The motion may close before all ballots are applied.
+				let voting = match Self::voting(room_id, index) {
+					Some(voting) => voting,
+					None => break,
+				};
+				let voters = if approve { &voting.ayes } else { &voting.nays };
+				if voters.contains(&who) {
+					Self::deposit_event(Event::SignedVoteSkipped(who, proposal));
+					continue
+				}
+				Self::do_vote(who, room_id, proposal, index, approve.into(), None, seats)?;
+			}
+			Ok(())
+		}
+
+		/// Disapprove every open motion of the room that carries the given proposal hash.
+		#[pallet::weight(1500_000_000)]
+		pub fn disapprove_proposal(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			proposal_hash: T::Hash,
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
-			let proposal_count = Self::do_disapprove_proposal(room_id, proposal_hash);
+			let mut proposal_count = 0;
+			for index in Self::proposals_by_hash(room_id, proposal_hash) {
+				proposal_count = Self::do_disapprove_proposal(
+					room_id,
+					index,
+					proposal_hash,
+					MotionOutcome::Overruled,
+				);
+			}
 			Ok(Some(T::WeightInfo::disapprove_proposal(proposal_count)).into())
 		}
+
+		/// Approve a motion before the end of its voting period.
+		///
+		/// Only the room root or the room prime can do this, and only once the ayes reach
+		/// `FastTrackThreshold` of the room council.
+		#[pallet::weight(1500_000_000)]
+		pub fn fast_track(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			proposal_hash: T::Hash,
+			#[pallet::compact] index: ProposalIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let room_owner = T::ListenHandler::get_root(room_id.into())?;
+			let prime = T::ListenHandler::get_prime(room_id.into())?;
+			ensure!(
+				room_owner == who || prime.as_ref() == Some(&who),
+				Error::<T, I>::NotRootOrPrime
+			);
+
+			let motion = Self::ensure_proposal_hash(room_id, index, &proposal_hash)?;
+			let voting = Self::voting(room_id, index).ok_or(Error::<T, I>::ProposalMissing)?;
+			ensure!(!Self::is_expire(&voting), Error::<T, I>::VoteExpire);
+
+			let council = T::ListenHandler::get_room_council(room_id.into())?;
+			let (yes_votes, no_votes) = Self::tally(room_id, index, &voting);
+			let max_votes = Self::max_votes_of(room_id, &council);
+			// with nothing to vote with, any threshold would be met without a single aye
+			ensure!(
+				max_votes > 0 &&
+					yes_votes > 0 && yes_votes >= T::FastTrackThreshold::get().mul_ceil(max_votes),
+				Error::<T, I>::NotSupermajority
+			);
+
+			Self::deposit_event(Event::FastTracked(who, proposal_hash, yes_votes));
+			if Self::do_approve_proposal(
+				room_id,
+				(yes_votes, max_votes),
+				voting,
+				index,
+				proposal_hash,
+				motion,
+			)
+			.is_some()
+			{
+				Self::deposit_event(Event::Closed(proposal_hash, yes_votes, no_votes));
+			}
+			Ok(())
+		}
+
+		/// Veto a motion as the room prime.
+		///
+		/// The motion is closed and its hash can not be proposed again for `VetoCoolOff` blocks.
+		/// The prime can veto a given proposal hash only once.
+		#[pallet::weight(1500_000_000)]
+		pub fn veto(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			proposal_hash: T::Hash,
+			#[pallet::compact] index: ProposalIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let prime = T::ListenHandler::get_prime(room_id.into())?;
+			ensure!(prime.as_ref() == Some(&who), Error::<T, I>::NotPrime);
+
+			Self::ensure_proposal_hash(room_id, index, &proposal_hash)?;
+
+			// Once the cool-off is over the hash counts as a new motion, which can be vetoed again.
+			let now = system::Pallet::<T>::block_number();
+			let mut vetoers = match Self::blacklist(room_id, proposal_hash) {
+				Some((until, vetoers)) if until > now => vetoers,
+				_ => vec![],
+			};
+			ensure!(!vetoers.contains(&who), Error::<T, I>::AlreadyVetoed);
+			vetoers.push(who.clone());
+
+			let until = now + T::VetoCoolOff::get();
+			<Blacklist<T, I>>::insert(room_id, proposal_hash, (until, vetoers));
+
+			Self::do_disapprove_proposal(room_id, index, proposal_hash, MotionOutcome::Overruled);
+			Self::deposit_event(Event::Vetoed(who, proposal_hash, until));
+			Ok(())
+		}
+
+		/// Leave the room council with a pro-rata share of the room's free amount, less the
+		/// approved spends that have not been paid yet.
+		///
+		/// Only members who voted nay on a spend motion that is still in its grace period can
+		/// rage-quit.
+		#[pallet::weight(1500_000_000)]
+		pub fn ragequit(origin: OriginFor<T>, room_id: RoomIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let members = T::ListenHandler::get_room_council(room_id.into())?;
+			ensure!(members.contains(&who), Error::<T, I>::NotMember);
+
+			let pending = <GracePeriods<T, I>>::iter_prefix(room_id).collect::<Vec<_>>();
+			let mut dissented = false;
+			for (index, (mut voting, execute_at)) in pending {
+				if let Some(pos) = voting.nays.iter().position(|a| a == &who) {
+					voting.nays.swap_remove(pos);
+					<GracePeriods<T, I>>::insert(room_id, index, (voting, execute_at));
+					dissented = true;
+				}
+			}
+			ensure!(dissented, Error::<T, I>::NotDissenter);
+
+			let uncommitted = T::ListenHandler::get_room_free_amount(room_id.into())
+				.saturating_sub(T::PendingSpends::pending_spends(room_id));
+			let payout = uncommitted / members.len() as u128;
+			T::ListenHandler::exit_room_council(room_id.into(), who.clone(), payout)?;
+
+			Self::deposit_event(Event::RageQuit(who, room_id, payout));
+			Ok(())
+		}
+
+		/// Choose how the members of the room vote. Only the room root can do this, and only
+		/// while the room has no open motions.
+		#[pallet::weight(1500_000_000)]
+		pub fn set_voting_mode(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			mode: VotingMode,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let room_owner = T::ListenHandler::get_root(room_id.into())?;
+			ensure!(room_owner == who, Error::<T, I>::NotRoomOwner);
+			ensure!(Self::proposals(room_id).is_empty(), Error::<T, I>::MotionsOpen);
+
+			<RoomVotingMode<T, I>>::insert(room_id, mode);
+			Self::deposit_event(Event::VotingModeSet(room_id, mode));
+			Ok(())
+		}
+
+		/// Cast `votes` votes on a motion of a quadratic voting room, spending `votes²` of the
+		/// member's voice credits of the current period.
+		///
+		/// A member votes once per motion.
+		#[pallet::weight(1500_000_000)]
+		pub fn vote_quadratic(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			proposal: T::Hash,
+			#[pallet::compact] index: ProposalIndex,
+			approve: bool,
+			#[pallet::compact] votes: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::voting_mode(room_id) == VotingMode::Quadratic,
+				Error::<T, I>::WrongVotingMode
+			);
+			let members = T::ListenHandler::get_room_council(room_id.into())?;
+			ensure!(members.contains(&who), Error::<T, I>::NotMember);
+			Self::ensure_proposal_hash(room_id, index, &proposal)?;
+
+			let mut voting = Self::voting(room_id, index).ok_or(Error::<T, I>::ProposalMissing)?;
+			ensure!(
+				!voting.ayes.contains(&who) && !voting.nays.contains(&who),
+				Error::<T, I>::DuplicateVote
+			);
+			ensure!(!votes.is_zero(), Error::<T, I>::ZeroVotes);
+
+			let period = system::Pallet::<T>::block_number()
+				.checked_div(&T::MotionDuration::get())
+				.unwrap_or_else(Zero::zero);
+			let (spent_period, spent) = Self::spent_voice_credits(room_id, &who);
+			let spent = if spent_period == period { spent } else { 0 };
+			let spent = votes
+				.checked_mul(votes)
+				.and_then(|cost| spent.checked_add(cost))
+				.filter(|spent| *spent <= T::VoiceCredits::get())
+				.ok_or(Error::<T, I>::InsufficientVoiceCredits)?;
+			<SpentVoiceCredits<T, I>>::insert(room_id, &who, (period, spent));
+
+			let (yes_votes, no_votes) = <QuadraticTally<T, I>>::mutate(room_id, index, |tally| {
+				if approve {
+					tally.0 = tally.0.saturating_add(votes);
+				} else {
+					tally.1 = tally.1.saturating_add(votes);
+				}
+				*tally
+			});
+			if approve {
+				voting.ayes.push(who.clone());
+			} else {
+				voting.nays.push(who.clone());
+			}
+			Voting::<T, I>::insert(room_id, index, voting.clone());
+
+			Self::deposit_event(Event::QuadraticVoted(
+				who, proposal, approve, votes, yes_votes, no_votes,
+			));
+			Self::normal_close(voting, room_id, index, proposal)
+		}
+
+		/// Forward a motion to the escalation collective once it is approved, instead of
+		/// executing it. It then only executes if the escalation collective approves it with
+		/// the given threshold. The motion stays open while the escalation collective refuses it.
+		///
+		/// Only the room root or the room prime can do this.
+		#[pallet::weight(1500_000_000)]
+		pub fn escalate(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			proposal_hash: T::Hash,
+			#[pallet::compact] index: ProposalIndex,
+			#[pallet::compact] threshold: MemberCount,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let room_owner = T::ListenHandler::get_root(room_id.into())?;
+			let prime = T::ListenHandler::get_prime(room_id.into())?;
+			ensure!(
+				room_owner == who || prime.as_ref() == Some(&who),
+				Error::<T, I>::NotRootOrPrime
+			);
+
+			Self::ensure_proposal_hash(room_id, index, &proposal_hash)?;
+			ensure!(<Voting<T, I>>::contains_key(room_id, index), Error::<T, I>::ProposalMissing);
+			ensure!(
+				!<Escalations<T, I>>::contains_key(room_id, index),
+				Error::<T, I>::AlreadyEscalated
+			);
+
+			<Escalations<T, I>>::insert(room_id, index, (who.clone(), threshold));
+			Self::deposit_event(Event::EscalationRequested(who, room_id, index, threshold));
+			Ok(())
+		}
+
+		/// Replace the metadata of an open motion as its proposer. The call of the motion can
+		/// not be changed.
+		///
+		/// With `reset_votes` every vote is cleared, except the proposer's aye in rooms with one
+		/// vote per member. Voice credits spent on the motion are not refunded.
+		#[pallet::weight(1500_000_000)]
+		pub fn amend(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] index: ProposalIndex,
+			metadata: MotionMetadata<T, I>,
+			reset_votes: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::proposer_of(room_id, index).as_ref() == Some(&who),
+				Error::<T, I>::NotProposer
+			);
+			let mut voting = Self::voting(room_id, index).ok_or(Error::<T, I>::ProposalMissing)?;
+			ensure!(!Self::is_expire(&voting), Error::<T, I>::VoteExpire);
+
+			<MetadataOf<T, I>>::insert(room_id, index, metadata.clone());
+			if reset_votes {
+				voting.ayes = match Self::voting_mode(room_id) {
+					VotingMode::OneMemberOneVote | VotingMode::Weighted => vec![who.clone()],
+					VotingMode::Quadratic => vec![],
+				};
+				voting.nays = vec![];
+				voting.abstentions = vec![];
+				<Voting<T, I>>::insert(room_id, index, voting);
+				<QuadraticTally<T, I>>::remove(room_id, index);
+			}
+
+			Self::deposit_event(Event::Amended(who, room_id, index, metadata, reset_votes));
+			Ok(())
+		}
+
+		/// Register a call that is proposed in the room every `period` blocks, at most
+		/// `repetitions` times. The first motion is proposed `period` blocks from now.
+		///
+		/// The room council votes every motion as usual, and every motion needs the votes of a
+		/// majority of the council, or of two members in smaller rooms. Only the room root or an
+		/// approved motion of the room can do this.
+		#[pallet::weight(1500_000_000)]
+		pub fn register_recurring(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] threshold: MemberCount,
+			proposal: Box<<T as Config<I>>::Proposal>,
+			period: T::BlockNumber,
+			#[pallet::compact] repetitions: u32,
+			#[pallet::compact] length_bound: u32,
+		) -> DispatchResult {
+			Self::ensure_room_authority(origin, room_id)?;
+			ensure!(!period.is_zero() && repetitions > 0, Error::<T, I>::WrongRecurrence);
+			let seats = T::ListenHandler::get_room_council(room_id.into())?.len() as MemberCount;
+			ensure!(
+				threshold >= 2 && threshold > seats / 2,
+				Error::<T, I>::RecurringThresholdTooLow
+			);
+			ensure!(
+				<T as pallet::Config<I>>::BaseCallFilter::contains(&proposal),
+				Error::<T, I>::DisallowFunc
+			);
+			let proposal_len = proposal.using_encoded(|x| x.len());
+			ensure!(proposal_len <= length_bound as usize, Error::<T, I>::WrongProposalLength);
+			ensure!(
+				<RecurringMotions<T, I>>::iter_prefix(room_id).count() <
+					T::MaxRecurringMotions::get() as usize,
+				Error::<T, I>::TooManyRecurringMotions
+			);
+
+			let id = Self::recurring_count(room_id);
+			let next = system::Pallet::<T>::block_number() + period;
+			let proposal_hash = T::Hashing::hash_of(&proposal);
+			<RecurringCount<T, I>>::insert(room_id, id + 1);
+			<RecurringMotions<T, I>>::insert(
+				room_id,
+				id,
+				RecurringMotion {
+					proposal: *proposal,
+					threshold,
+					period,
+					remaining: repetitions,
+					next,
+				},
+			);
+			<RecurringQueue<T, I>>::append(next, (room_id, id));
+
+			Self::deposit_event(Event::RecurringRegistered(room_id, id, proposal_hash));
+			Ok(())
+		}
+
+		/// Stop proposing a recurring motion. Motions already proposed stay open.
+		///
+		/// Only the room root or an approved motion of the room can do this.
+		#[pallet::weight(1500_000_000)]
+		pub fn cancel_recurring(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] id: u32,
+		) -> DispatchResult {
+			Self::ensure_room_authority(origin, room_id)?;
+			let recurring = <RecurringMotions<T, I>>::take(room_id, id)
+				.ok_or(Error::<T, I>::RecurringMissing)?;
+			<RecurringQueue<T, I>>::mutate(recurring.next, |queued| {
+				queued.retain(|entry| entry != &(room_id, id))
+			});
+
+			Self::deposit_event(Event::RecurringCancelled(room_id, id));
+			Ok(())
+		}
+
+		/// Introduce a motion that needs the approval of a majority of the councils of several
+		/// rooms.
+		///
+		/// The proposer must sit on the council of at least one of the rooms and votes aye in
+		/// each of them. Every member of a room council has one vote in the room's tally,
+		/// whatever the voting mode of the room. Once every room approved it, the call is
+		/// dispatched with `RoomRawOrigin::Rooms`. A room that is dissolved meanwhile drops out
+		/// of the motion.
+		#[pallet::weight(1500_000_000)]
+		#[transactional]
+		pub fn propose_cross_room(
+			origin: OriginFor<T>,
+			rooms: Vec<RoomIndex>,
+			proposal: Box<<T as Config<I>>::Proposal>,
+			#[pallet::compact] length_bound: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				rooms.len() >= 2 && rooms.len() <= T::MaxCrossRooms::get() as usize,
+				Error::<T, I>::WrongRoomCount
+			);
+			ensure!(
+				rooms.iter().all(|room_id| !<DissolvingRooms<T, I>>::contains_key(room_id)),
+				Error::<T, I>::RoomDissolving
+			);
+			ensure!(
+				<T as pallet::Config<I>>::BaseCallFilter::contains(&proposal),
+				Error::<T, I>::DisallowFunc
+			);
+			let proposal_len = proposal.using_encoded(|x| x.len());
+			ensure!(proposal_len <= length_bound as usize, Error::<T, I>::WrongProposalLength);
+
+			let index = Self::cross_room_count();
+			let end = system::Pallet::<T>::block_number() + T::MotionDuration::get();
+			let mut room_ids = Vec::with_capacity(rooms.len());
+			let mut tallies = Vec::with_capacity(rooms.len());
+			for room_id in rooms {
+				ensure!(!room_ids.contains(&room_id), Error::<T, I>::DuplicateRoom);
+				room_ids.push(room_id);
+
+				let members = T::ListenHandler::get_room_council(room_id.into())?;
+				let ayes = if members.contains(&who) { vec![who.clone()] } else { vec![] };
+				let votes = ListenDaoVotes {
+					index,
+					threshold: members.len() as MemberCount / 2 + 1,
+					ayes,
+					nays: vec![],
+					abstentions: vec![],
+					end,
+				};
+				tallies.push((room_id, votes));
+			}
+			ensure!(
+				tallies.iter().any(|(_, votes)| !votes.ayes.is_empty()),
+				Error::<T, I>::NotMember
+			);
+
+			for (room_id, votes) in tallies {
+				<CrossRoomVoting<T, I>>::insert(index, room_id, votes);
+				<CrossRoomsOf<T, I>>::insert(room_id, index, ());
+			}
+			let proposal_hash = T::Hashing::hash_of(&proposal);
+			<CrossRoomCount<T, I>>::put(index + 1);
+			let motion = CrossRoomMotion {
+				proposer: who.clone(),
+				proposal: *proposal,
+				rooms: room_ids.clone(),
+				approved: vec![],
+				end,
+			};
+			Self::deposit_event(Event::CrossRoomProposed(who, index, proposal_hash, room_ids));
+
+			Self::update_cross_room(index, motion)
+		}
+
+		/// Vote on a cross-room motion as a member of the council of one of its rooms. The vote
+		/// only counts towards the tally of that room.
+		#[pallet::weight(1500_000_000)]
+		#[transactional]
+		pub fn vote_cross_room(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ProposalIndex,
+			room_id: RoomIndex,
+			ballot: Ballot,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let motion = Self::cross_room_motions(index).ok_or(Error::<T, I>::CrossRoomMissing)?;
+			ensure!(motion.rooms.contains(&room_id), Error::<T, I>::NotParticipatingRoom);
+			ensure!(
+				!motion.approved.iter().any(|(id, ..)| *id == room_id),
+				Error::<T, I>::RoomAlreadyApproved
+			);
+			ensure!(motion.end > system::Pallet::<T>::block_number(), Error::<T, I>::VoteExpire);
+			let members = T::ListenHandler::get_room_council(room_id.into())?;
+			ensure!(members.contains(&who), Error::<T, I>::NotMember);
+
+			let mut voting =
+				Self::cross_room_voting(index, room_id).ok_or(Error::<T, I>::CrossRoomMissing)?;
+			let voters = match ballot {
+				Ballot::Aye => &voting.ayes,
+				Ballot::Nay => &voting.nays,
+				Ballot::Abstain => &voting.abstentions,
+			};
+			ensure!(!voters.contains(&who), Error::<T, I>::DuplicateVote);
+
+			voting.ayes.retain(|a| a != &who);
+			voting.nays.retain(|a| a != &who);
+			voting.abstentions.retain(|a| a != &who);
+			match ballot {
+				Ballot::Aye => voting.ayes.push(who.clone()),
+				Ballot::Nay => voting.nays.push(who.clone()),
+				Ballot::Abstain => voting.abstentions.push(who.clone()),
+			}
+			let yes_votes = voting.ayes.len() as MemberCount;
+			let no_votes = voting.nays.len() as MemberCount;
+			<CrossRoomVoting<T, I>>::insert(index, room_id, voting);
+
+			Self::deposit_event(Event::CrossRoomVoted(
+				who, index, room_id, ballot, yes_votes, no_votes,
+			));
+			Self::update_cross_room(index, motion)
+		}
+
+		/// Reject a cross-room motion that some room did not approve before its end. Any
+		/// account can do this.
+		#[pallet::weight(1500_000_000)]
+		pub fn close_cross_room(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ProposalIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let motion = Self::cross_room_motions(index).ok_or(Error::<T, I>::CrossRoomMissing)?;
+			ensure!(
+				motion.end <= system::Pallet::<T>::block_number(),
+				Error::<T, I>::VoteNotExpired
+			);
+
+			Self::remove_cross_room(index, &motion.rooms);
+			Self::deposit_event(Event::CrossRoomRejected(index, None));
+			Ok(())
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// The hash a motion is known by. A single call keeps the hash of the call itself.
+		pub fn motion_hash(motion: &RoomMotion<<T as Config<I>>::Proposal>) -> T::Hash {
+			match motion {
+				RoomMotion::Single(proposal) => T::Hashing::hash_of(proposal),
+				RoomMotion::Batch(..) => T::Hashing::hash_of(motion),
+			}
+		}
+
+		/// Check the consistency of the motion storage.
+		///
+		/// - Every index in `Proposals` has a `ProposalOf` and a `Voting` entry.
+		/// - Every `Voting` index is below the room's `ProposalCount`.
+		/// - No account is in more than one of the ayes, nays and abstentions of a motion.
+		/// - No room has more than `MaxProposals` open motions.
+		/// - Every room of an open cross-room motion has a tally.
+		/// - Every motion in its grace period has a `ProposalOf` entry and is no longer open.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), &'static str> {
+			for (room_id, proposals) in <Proposals<T, I>>::iter() {
+				ensure!(
+					proposals.len() <= T::MaxProposals::get() as usize,
+					"Room has more than `MaxProposals` open motions"
+				);
+				for index in proposals {
+					ensure!(
+						<ProposalOf<T, I>>::contains_key(room_id, index),
+						"Open motion has no `ProposalOf` entry"
+					);
+					ensure!(
+						<Voting<T, I>>::contains_key(room_id, index),
+						"Open motion has no `Voting` entry"
+					);
+				}
+			}
+
+			for (room_id, index, voting) in <Voting<T, I>>::iter() {
+				ensure!(voting.index == index, "`Voting` is stored under another index");
+				ensure!(
+					voting.index < Self::proposal_count(room_id),
+					"`Voting` index is not below `ProposalCount`"
+				);
+				ensure!(
+					!voting.ayes.iter().any(|who| voting.nays.contains(who)),
+					"Account voted both aye and nay"
+				);
+				ensure!(
+					!voting
+						.abstentions
+						.iter()
+						.any(|who| voting.ayes.contains(who) || voting.nays.contains(who)),
+					"Account both voted and abstained"
+				);
+			}
+
+			for (room_id, index, (voting, _)) in <GracePeriods<T, I>>::iter() {
+				// The motions of a dissolving room are removed one map at a time.
+				if <DissolvingRooms<T, I>>::contains_key(room_id) {
+					continue
+				}
+				ensure!(voting.index == index, "`GracePeriods` is stored under another index");
+				ensure!(
+					<ProposalOf<T, I>>::contains_key(room_id, index),
+					"Motion in its grace period has no `ProposalOf` entry"
+				);
+				ensure!(
+					!<Voting<T, I>>::contains_key(room_id, index) &&
+						!Self::proposals(room_id).contains(&index),
+					"Motion in its grace period is still open"
+				);
+			}
+
+			for (index, motion) in <CrossRoomMotions<T, I>>::iter() {
+				ensure!(
+					index < Self::cross_room_count(),
+					"Cross-room index is not below the count"
+				);
+				for room_id in motion.rooms.iter() {
+					ensure!(
+						<CrossRoomVoting<T, I>>::contains_key(index, room_id),
+						"Participating room has no `CrossRoomVoting` entry"
+					);
+					ensure!(
+						<CrossRoomsOf<T, I>>::contains_key(room_id, index),
+						"Participating room has no `CrossRoomsOf` entry"
+					);
+				}
+				ensure!(
+					motion.approved.iter().all(|(room_id, ..)| motion.rooms.contains(room_id)),
+					"Approving room does not take part in the cross-room motion"
+				);
+			}
+
+			Ok(())
+		}
+
+		// Returns the motion at `index`, making sure it carries the given proposal hash.
+		fn ensure_proposal_hash(
+			room_id: RoomIndex,
+			index: ProposalIndex,
+			proposal_hash: &T::Hash,
+		) -> result::Result<RoomMotion<<T as Config<I>>::Proposal>, DispatchError> {
+			let motion = Self::proposal_of(room_id, index).ok_or(Error::<T, I>::ProposalMissing)?;
+			ensure!(&Self::motion_hash(&motion) == proposal_hash, Error::<T, I>::WrongIndex);
+			Ok(motion)
+		}
+
+		fn do_propose(
+			who: T::AccountId,
+			room_id: RoomIndex,
+			threshold: MemberCount,
+			motion: RoomMotion<<T as Config<I>>::Proposal>,
+			metadata: MotionMetadata<T, I>,
+		) -> DispatchResult {
+			ensure!(!<DissolvingRooms<T, I>>::contains_key(room_id), Error::<T, I>::RoomDissolving);
+			let members = T::ListenHandler::get_room_council(room_id.into())?;
+			ensure!(members.contains(&who), Error::<T, I>::NotMember);
+
+			let proposal_hash = Self::motion_hash(&motion);
+			Self::ensure_not_blacklisted(room_id, proposal_hash)?;
+
+			if threshold < 2 {
+				let seats = members.len() as MemberCount;
+				Self::do_execute_motion(room_id, 1, seats, proposal_hash, motion);
+				Ok(())
+			} else {
+				// In quadratic voting rooms the proposer spends voice credits like everyone else.
+				let ayes = match Self::voting_mode(room_id) {
+					VotingMode::OneMemberOneVote | VotingMode::Weighted => vec![who.clone()],
+					VotingMode::Quadratic => vec![],
+				};
+				Self::insert_motion(
+					Some(who),
+					room_id,
+					threshold,
+					proposal_hash,
+					motion,
+					metadata,
+					ayes,
+				)?;
+				Ok(())
+			}
+		}
+
+		// Fails while the hash is cooling off from a veto, and forgets the vetoers once it is not.
+		fn ensure_not_blacklisted(room_id: RoomIndex, proposal_hash: T::Hash) -> DispatchResult {
+			if let Some((until, _)) = Self::blacklist(room_id, proposal_hash) {
+				ensure!(
+					until <= system::Pallet::<T>::block_number(),
+					Error::<T, I>::ProposalBlacklisted
+				);
+				<Blacklist<T, I>>::remove(room_id, proposal_hash);
+			}
+			Ok(())
+		}
+
+		// Opens a motion for voting and returns its index. Motions forwarded from another
+		// collective have no proposer.
+		fn insert_motion(
+			proposer: Option<T::AccountId>,
+			room_id: RoomIndex,
+			threshold: MemberCount,
+			proposal_hash: T::Hash,
+			motion: RoomMotion<<T as Config<I>>::Proposal>,
+			metadata: MotionMetadata<T, I>,
+			ayes: Vec<T::AccountId>,
+		) -> result::Result<ProposalIndex, DispatchError> {
+			let index = Self::proposal_count(room_id);
+			let _active_proposals = <Proposals<T, I>>::try_mutate(
+				room_id,
+				|proposals| -> Result<usize, DispatchError> {
+					proposals.push(index);
+					ensure!(
+						proposals.len() <= T::MaxProposals::get() as usize,
+						Error::<T, I>::TooManyProposals
+					);
+					Ok(proposals.len())
+				},
+			)?;
+			ProposalCount::<T, I>::mutate(room_id, |i| *i += 1);
+			<ProposalOf<T, I>>::insert(room_id, index, motion);
+			<ProposalsByHash<T, I>>::append(room_id, proposal_hash, index);
+			<MetadataOf<T, I>>::insert(room_id, index, metadata);
+			if let Some(who) = &proposer {
+				<ProposerOf<T, I>>::insert(room_id, index, who);
+			}
+			let end = system::Pallet::<T>::block_number() + T::MotionDuration::get();
+			let votes =
+				ListenDaoVotes { index, threshold, ayes, nays: vec![], abstentions: vec![], end };
+			<Voting<T, I>>::insert(room_id, index, votes);
+
+			if let Some(who) = proposer {
+				Self::deposit_event(Event::Proposed(who, index, proposal_hash, threshold));
+			}
+			Ok(index)
+		}
+
+		fn do_vote(
+			who: T::AccountId,
+			room_id: RoomIndex,
+			proposal: T::Hash,
+			index: ProposalIndex,
+			ballot: Ballot,
+			rationale_hash: Option<T::Hash>,
+			seats: MemberCount,
+		) -> DispatchResult {
+			ensure!(
+				Self::voting_mode(room_id) != VotingMode::Quadratic,
+				Error::<T, I>::WrongVotingMode
+			);
+			let mut voting = Self::voting(room_id, index).ok_or(Error::<T, I>::ProposalMissing)?;
+
+			let voters = match ballot {
+				Ballot::Aye => &voting.ayes,
+				Ballot::Nay => &voting.nays,
+				Ballot::Abstain => &voting.abstentions,
+			};
+			ensure!(!voters.contains(&who), Error::<T, I>::DuplicateVote);
+
+			voting.ayes.retain(|a| a != &who);
+			voting.nays.retain(|a| a != &who);
+			voting.abstentions.retain(|a| a != &who);
+			match ballot {
+				Ballot::Aye => voting.ayes.push(who.clone()),
+				Ballot::Nay => voting.nays.push(who.clone()),
+				Ballot::Abstain => voting.abstentions.push(who.clone()),
+			}
+
+			let (yes_votes, no_votes) = Self::tally(room_id, index, &voting);
+			let abstentions = voting.abstentions.len() as MemberCount;
+			Self::deposit_event(Event::Voted(
+				who,
+				proposal,
+				ballot,
+				seats,
+				yes_votes,
+				no_votes,
+				abstentions,
+				rationale_hash,
+			));
+			Voting::<T, I>::insert(room_id, index, voting.clone());
+
+			Self::normal_close(voting.clone(), room_id, index, proposal)?;
+			Ok(())
+		}
+
+		fn signed_vote_payload(
+			room_id: RoomIndex,
+			proposal: &T::Hash,
+			index: ProposalIndex,
+			approve: bool,
+			nonce: u64,
+		) -> Vec<u8> {
+			let genesis_hash = system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+			(
+				SIGNED_VOTE_CONTEXT,
+				<Pallet<T, I> as PalletInfoAccess>::name().as_bytes(),
+				genesis_hash,
+				room_id,
+				proposal,
+				index,
+				approve,
+				nonce,
+			)
+				.encode()
+		}
+
 		fn normal_close(
 			voting: ListenDaoVotes<T::AccountId, T::BlockNumber>,
 			room_id: RoomIndex,
+			index: ProposalIndex,
 			proposal_hash: T::Hash,
 		) -> DispatchResult {
-			let no_votes = voting.nays.len() as MemberCount;
-			let yes_votes = voting.ayes.len() as MemberCount;
-			let seats = T::ListenHandler::get_room_council(room_id.into())?.len() as MemberCount;
+			let council = T::ListenHandler::get_room_council(room_id.into())?;
+			let (yes_votes, no_votes) = Self::tally(room_id, index, &voting);
 
-			let result: (IsEnd, IsPass) = Self::vote_result(&voting, room_id)?;
+			let result: (IsEnd, IsPass) = Self::vote_result(&voting, room_id, index)?;
 
 			if result.0 {
 				if result.1 {
-					let proposal = ProposalOf::<T, I>::get(room_id, proposal_hash)
+					let motion = ProposalOf::<T, I>::get(room_id, index)
 						.ok_or(Error::<T, I>::ProposalMissing)?;
-					Self::do_approve_proposal(room_id, seats, voting, proposal_hash, proposal);
-					Self::deposit_event(Event::Closed(proposal_hash, yes_votes, no_votes));
+					if Self::do_approve_proposal(
+						room_id,
+						(yes_votes, Self::max_votes_of(room_id, &council)),
+						voting,
+						index,
+						proposal_hash,
+						motion,
+					)
+					.is_some()
+					{
+						Self::deposit_event(Event::Closed(proposal_hash, yes_votes, no_votes));
+					}
 				} else {
-					Self::do_disapprove_proposal(room_id, proposal_hash);
+					Self::do_disapprove_proposal(
+						room_id,
+						index,
+						proposal_hash,
+						MotionOutcome::Rejected,
+					);
 					if Self::is_expire(&voting) {
 						return Err(Error::<T, I>::VoteExpire)?
 					}
@@ -453,17 +1878,62 @@ pub mod pallet {
 			false
 		}
 
+		// Summed yes and no votes of a motion. Every member counts once unless the room votes
+		// quadratically or weighs votes.
+		fn tally(
+			room_id: RoomIndex,
+			index: ProposalIndex,
+			voting: &ListenDaoVotes<T::AccountId, T::BlockNumber>,
+		) -> (u32, u32) {
+			match Self::voting_mode(room_id) {
+				VotingMode::OneMemberOneVote =>
+					(voting.ayes.len() as MemberCount, voting.nays.len() as MemberCount),
+				VotingMode::Quadratic => Self::quadratic_tally(room_id, index),
+				VotingMode::Weighted => (
+					Self::vote_weight(room_id, &voting.ayes),
+					Self::vote_weight(room_id, &voting.nays),
+				),
+			}
+		}
+
+		// The most votes the given members can cast on a motion together.
+		fn max_votes_of(room_id: RoomIndex, members: &[T::AccountId]) -> u32 {
+			let seats = members.len() as MemberCount;
+			match Self::voting_mode(room_id) {
+				VotingMode::OneMemberOneVote => seats,
+				VotingMode::Quadratic =>
+					seats.saturating_mul(T::VoiceCredits::get().integer_sqrt()),
+				VotingMode::Weighted => Self::vote_weight(room_id, members),
+			}
+		}
+
+		fn vote_weight(room_id: RoomIndex, members: &[T::AccountId]) -> u32 {
+			members
+				.iter()
+				.fold(0u64, |weight, who| {
+					weight.saturating_add(T::VoteWeight::vote_weight(room_id, who))
+				})
+				.saturated_into()
+		}
+
 		fn vote_result(
 			voting: &ListenDaoVotes<T::AccountId, T::BlockNumber>,
 			room_id: RoomIndex,
+			index: ProposalIndex,
 		) -> result::Result<(IsEnd, IsPass), DispatchError> {
-			let no_votes = voting.nays.len() as MemberCount;
-			let yes_votes = voting.ayes.len() as MemberCount;
-			let seats = T::ListenHandler::get_room_council(room_id.into())?.len() as MemberCount;
+			let (yes_votes, _) = Self::tally(room_id, index, voting);
+			let not_voted: Vec<_> = T::ListenHandler::get_room_council(room_id.into())?
+				.into_iter()
+				.filter(|who| {
+					!voting.ayes.contains(who) &&
+						!voting.nays.contains(who) &&
+						!voting.abstentions.contains(who)
+				})
+				.collect();
+			let max_yes_votes = yes_votes.saturating_add(Self::max_votes_of(room_id, &not_voted));
 
 			let approved = yes_votes >= voting.threshold;
-			let disapproved =
-				seats.saturating_sub(no_votes) < voting.threshold || Self::is_expire(&voting);
+			let disapproved = max_yes_votes < voting.threshold || Self::is_expire(&voting);
 			if approved || disapproved {
 				if approved {
 					Ok((true, true))
@@ -475,47 +1945,494 @@ pub mod pallet {
 			}
 		}
 
+		/// `votes` are the yes votes that decided the motion, out of all the votes the council
+		/// could cast; the motion is executed with them.
 		fn do_approve_proposal(
 			room_id: RoomIndex,
-			seats: MemberCount,
+			votes: (MemberCount, MemberCount),
 			voting: ListenDaoVotes<T::AccountId, T::BlockNumber>,
+			index: ProposalIndex,
 			proposal_hash: T::Hash,
-			proposal: <T as Config<I>>::Proposal,
-		) -> u32 {
+			motion: RoomMotion<<T as Config<I>>::Proposal>,
+		) -> Option<u32> {
+			// The motion stays open here until the escalation collective accepts it; the vote
+			// that approved it still counts.
+			if let Some((_, threshold)) = Self::escalations(room_id, index) {
+				let escalated = match T::Escalation::escalate(room_id, index, motion, threshold) {
+					Ok(escalated) => escalated,
+					Err(e) => {
+						Self::deposit_event(Event::EscalationFailed(
+							room_id,
+							index,
+							proposal_hash,
+							e,
+						));
+						return None
+					},
+				};
+				Self::deposit_event(Event::Approved(proposal_hash));
+				Self::notify_closed(room_id, index, MotionOutcome::Approved, &voting);
+				Self::deposit_event(Event::Escalated(room_id, index, proposal_hash, escalated));
+				return Some(Self::remove_proposal(room_id, index, proposal_hash))
+			}
+
 			Self::deposit_event(Event::Approved(proposal_hash));
+			Self::notify_closed(room_id, index, MotionOutcome::Approved, &voting);
+
+			if motion.calls().iter().any(|call| T::SpendCalls::contains(call)) &&
+				!T::GracePeriod::get().is_zero()
+			{
+				return Some(Self::start_grace_period(room_id, voting, index, proposal_hash))
+			}
+
+			Self::do_execute_motion(room_id, votes.0, votes.1, proposal_hash, motion);
 
-			let _dispatch_weight = proposal.get_dispatch_info().weight;
+			let proposal_count = Self::remove_proposal(room_id, index, proposal_hash);
+			Some(proposal_count)
+		}
+
+		fn do_execute_motion(
+			room_id: RoomIndex,
+			yes_votes: MemberCount,
+			seats: MemberCount,
+			proposal_hash: T::Hash,
+			motion: RoomMotion<<T as Config<I>>::Proposal>,
+		) -> Weight {
+			match motion {
+				RoomMotion::Single(proposal) =>
+					Self::do_execute_proposal(room_id, yes_votes, seats, proposal_hash, proposal),
+				RoomMotion::Batch(calls, mode) =>
+					Self::do_execute_batch(room_id, yes_votes, seats, proposal_hash, calls, mode),
+			}
+		}
+
+		fn do_execute_batch(
+			room_id: RoomIndex,
+			yes_votes: MemberCount,
+			seats: MemberCount,
+			proposal_hash: T::Hash,
+			calls: Vec<<T as Config<I>>::Proposal>,
+			mode: BatchMode,
+		) -> Weight {
+			let dispatch_weight = calls.iter().fold(0 as Weight, |weight, call| {
+				weight.saturating_add(call.get_dispatch_info().weight)
+			});
+
+			let result = match mode {
+				BatchMode::AllOrNothing => {
+					let mut failed_at = 0;
+					Self::dispatch_all(room_id, yes_votes, seats, calls, &mut failed_at)
+						.map_err(|e| (failed_at, e))
+				},
+				BatchMode::BestEffort => {
+					let mut first_error = None;
+					for (i, call) in calls.into_iter().enumerate() {
+						if let Err(e) = Self::dispatch_one(room_id, yes_votes, seats, call) {
+							first_error = first_error.or(Some((i as u32, e)));
+						}
+					}
+					first_error.map_or(Ok(()), Err)
+				},
+			};
+
+			Self::deposit_event(Event::Executed(proposal_hash, result));
+			dispatch_weight
+		}
+
+		// Dispatches the calls in order and reverts all of them as soon as one fails.
+		#[transactional]
+		fn dispatch_all(
+			room_id: RoomIndex,
+			yes_votes: MemberCount,
+			seats: MemberCount,
+			calls: Vec<<T as Config<I>>::Proposal>,
+			failed_at: &mut u32,
+		) -> DispatchResult {
+			for (i, call) in calls.into_iter().enumerate() {
+				*failed_at = i as u32;
+				Self::dispatch_one(room_id, yes_votes, seats, call)?;
+			}
+			Ok(())
+		}
+
+		#[transactional]
+		fn dispatch_one(
+			room_id: RoomIndex,
+			yes_votes: MemberCount,
+			seats: MemberCount,
+			call: <T as Config<I>>::Proposal,
+		) -> DispatchResult {
+			call.dispatch(RoomRawOrigin::RoomMembers(room_id, yes_votes, seats).into())
+				.map(|_| ())
+				.map_err(|e| e.error)
+		}
+
+		fn do_execute_proposal(
+			room_id: RoomIndex,
+			yes_votes: MemberCount,
+			seats: MemberCount,
+			proposal_hash: T::Hash,
+			proposal: <T as Config<I>>::Proposal,
+		) -> Weight {
+			let dispatch_weight = proposal.get_dispatch_info().weight;
 
 			// let origin = RoomRawOrigin::Members(voting.threshold, seats).into();
-			let origin = RoomRawOrigin::Members(voting.ayes.len() as MemberCount, seats).into();
+			let origin = RoomRawOrigin::RoomMembers(room_id, yes_votes, seats).into();
 
 			let result = proposal.dispatch(origin);
 			Self::deposit_event(Event::Executed(
 				proposal_hash,
-				result.map(|_| ()).map_err(|e| e.error),
+				result.map(|_| ()).map_err(|e| (0, e.error)),
 			));
+			dispatch_weight
+		}
+
+		// Moves an approved spend motion out of the active proposals until its grace period ends.
+		fn start_grace_period(
+			room_id: RoomIndex,
+			voting: ListenDaoVotes<T::AccountId, T::BlockNumber>,
+			index: ProposalIndex,
+			proposal_hash: T::Hash,
+		) -> u32 {
+			let execute_at = system::Pallet::<T>::block_number() + T::GracePeriod::get();
+			<GraceQueue<T, I>>::append(execute_at, (room_id, index));
+			<GracePeriods<T, I>>::insert(room_id, index, (voting, execute_at));
+			// the quadratic tally is kept to execute the motion with once the grace period ends
+			Voting::<T, I>::remove(room_id, index);
+			let num_proposals = Proposals::<T, I>::mutate(room_id, |proposals| {
+				proposals.retain(|i| i != &index);
+				proposals.len() + 1 // calculate weight based on original length
+			});
+
+			Self::deposit_event(Event::GracePeriodStarted(proposal_hash, execute_at));
+			num_proposals as u32
+		}
 
-			let proposal_count = Self::remove_proposal(room_id, proposal_hash);
-			proposal_count
+		fn end_grace_period(room_id: RoomIndex, index: ProposalIndex) -> Weight {
+			let db_weight = T::DbWeight::get().reads_writes(5, 5);
+			// The motion may have been disapproved or the room dissolved in the meantime.
+			let (voting, _) = match <GracePeriods<T, I>>::take(room_id, index) {
+				Some(grace) => grace,
+				None => return db_weight,
+			};
+			let motion = match ProposalOf::<T, I>::take(room_id, index) {
+				Some(motion) => motion,
+				None => return db_weight,
+			};
+			let proposal_hash = Self::motion_hash(&motion);
+			let (yes_votes, _) = Self::tally(room_id, index, &voting);
+			QuadraticTally::<T, I>::remove(room_id, index);
+			Self::remove_hash_index(room_id, index, proposal_hash);
+			MetadataOf::<T, I>::remove(room_id, index);
+			ProposerOf::<T, I>::remove(room_id, index);
+			let max_votes = match T::ListenHandler::get_room_council(room_id.into()) {
+				Ok(members) if !<DissolvingRooms<T, I>>::contains_key(room_id) =>
+					Self::max_votes_of(room_id, &members),
+				_ => {
+					Self::deposit_event(Event::Disapproved(proposal_hash));
+					return db_weight
+				},
+			};
+
+			db_weight.saturating_add(Self::do_execute_motion(
+				room_id,
+				yes_votes,
+				max_votes,
+				proposal_hash,
+				motion,
+			))
 		}
 
-		fn do_disapprove_proposal(room_id: RoomIndex, proposal_hash: T::Hash) -> u32 {
+		fn do_disapprove_proposal(
+			room_id: RoomIndex,
+			index: ProposalIndex,
+			proposal_hash: T::Hash,
+			outcome: MotionOutcome,
+		) -> u32 {
 			// disapproved
 			Self::deposit_event(Event::Disapproved(proposal_hash));
-			Self::remove_proposal(room_id, proposal_hash)
+			if let Some(voting) = Self::voting(room_id, index) {
+				Self::notify_closed(room_id, index, outcome, &voting);
+			}
+			Self::remove_proposal(room_id, index, proposal_hash)
+		}
+
+		fn notify_closed(
+			room_id: RoomIndex,
+			index: ProposalIndex,
+			outcome: MotionOutcome,
+			voting: &ListenDaoVotes<T::AccountId, T::BlockNumber>,
+		) {
+			T::Observer::on_motion_closed(
+				room_id,
+				Self::proposer_of(room_id, index),
+				outcome,
+				&voting.ayes,
+				&voting.nays,
+				&voting.abstentions,
+			);
 		}
 
 		// Removes a proposal from the pallet, cleaning up votes and the vector of proposals.
-		fn remove_proposal(room_id: RoomIndex, proposal_hash: T::Hash) -> u32 {
+		fn remove_proposal(
+			room_id: RoomIndex,
+			index: ProposalIndex,
+			proposal_hash: T::Hash,
+		) -> u32 {
 			// remove proposal and vote
-			ProposalOf::<T, I>::remove(room_id, &proposal_hash);
-			Voting::<T, I>::remove(room_id, &proposal_hash);
+			ProposalOf::<T, I>::remove(room_id, index);
+			Voting::<T, I>::remove(room_id, index);
+			QuadraticTally::<T, I>::remove(room_id, index);
+			Escalations::<T, I>::remove(room_id, index);
+			EscalatedFrom::<T, I>::remove(room_id, index);
+			GracePeriods::<T, I>::remove(room_id, index);
+			MetadataOf::<T, I>::remove(room_id, index);
+			ProposerOf::<T, I>::remove(room_id, index);
+			Self::remove_hash_index(room_id, index, proposal_hash);
 			let num_proposals = Proposals::<T, I>::mutate(room_id, |proposals| {
-				proposals.retain(|h| h != &proposal_hash);
+				proposals.retain(|i| i != &index);
 				proposals.len() + 1 // calculate weight based on original length
 			});
 			num_proposals as u32
 		}
+
+		// Accepts the room root, signed or through `execute`, and the motions approved by the
+		// room council.
+		fn ensure_room_authority(origin: OriginFor<T>, room_id: RoomIndex) -> DispatchResult {
+			let origin = <T as Config<I>>::Origin::from(origin);
+			let room_origin: Result<RoomRawOrigin<T::AccountId, I>, <T as Config<I>>::Origin> =
+				origin.into();
+			let who = match room_origin {
+				Ok(RoomRawOrigin::RoomMembers(id, ..)) if id == room_id => return Ok(()),
+				Ok(RoomRawOrigin::Member(id, who)) if id == room_id => who,
+				Ok(_) => return Err(DispatchError::BadOrigin),
+				Err(origin) => ensure_signed(Into::<OriginFor<T>>::into(origin))?,
+			};
+			let room_owner = T::ListenHandler::get_root(room_id.into())?;
+			ensure!(room_owner == who, Error::<T, I>::NotRoomOwner);
+			Ok(())
+		}
+
+		// Proposes a due recurring motion on behalf of the room root and schedules the next one.
+		fn propose_recurring(room_id: RoomIndex, id: u32) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(2, 2);
+			let mut recurring = match Self::recurring_motions(room_id, id) {
+				Some(recurring) => recurring,
+				None => return weight,
+			};
+			if <DissolvingRooms<T, I>>::contains_key(room_id) {
+				return weight
+			}
+
+			let motion = RoomMotion::Single(recurring.proposal.clone());
+			let proposal_hash = Self::motion_hash(&motion);
+			let result = T::ListenHandler::get_root(room_id.into()).and_then(|root| {
+				Self::ensure_not_blacklisted(room_id, proposal_hash)?;
+				// Nobody votes for the motion on proposal, the whole council votes every time.
+				Self::insert_motion(
+					Some(root),
+					room_id,
+					recurring.threshold,
+					proposal_hash,
+					motion,
+					Default::default(),
+					vec![],
+				)
+			});
+			if result.is_ok() {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 6));
+			}
+			Self::deposit_event(Event::RecurringProposed(room_id, id, result));
+
+			recurring.remaining = recurring.remaining.saturating_sub(1);
+			if recurring.remaining.is_zero() {
+				<RecurringMotions<T, I>>::remove(room_id, id);
+			} else {
+				recurring.next = recurring.next + recurring.period;
+				<RecurringQueue<T, I>>::append(recurring.next, (room_id, id));
+				<RecurringMotions<T, I>>::insert(room_id, id, recurring);
+			}
+			weight
+		}
+
+		// Removes at most `limit` items of the storage of a dissolved room, and the room's
+		// remaining entries once every map is cleared.
+		fn continue_dissolution(room_id: RoomIndex, mut limit: u32) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let (mut stage, mut cursor) = match Self::dissolving_rooms(room_id) {
+				Some(progress) => progress,
+				None => return weight,
+			};
+
+			while limit > 0 {
+				let result = match Self::clear_room_map(room_id, stage, limit, cursor.as_deref()) {
+					Some(result) => result,
+					None => {
+						<DissolvingRooms<T, I>>::remove(room_id);
+						<Proposals<T, I>>::remove(room_id);
+						<ProposalCount<T, I>>::remove(room_id);
+						<RoomVotingMode<T, I>>::remove(room_id);
+						<RecurringCount<T, I>>::remove(room_id);
+						Self::deposit_event(Event::RoomDissolved(room_id));
+						return weight.saturating_add(T::DbWeight::get().writes(4))
+					},
+				};
+				limit = limit.saturating_sub(result.loops);
+				weight = weight.saturating_add(
+					T::DbWeight::get().reads_writes(result.loops as u64 + 1, result.backend as u64),
+				);
+				cursor = result.maybe_cursor;
+				if cursor.is_none() {
+					stage += 1;
+				}
+			}
+
+			<DissolvingRooms<T, I>>::insert(room_id, (stage, cursor));
+			weight
+		}
+
+		// Clears the entries of a room from the map at `stage`, or returns `None` once every map
+		// has been cleared.
+		fn clear_room_map(
+			room_id: RoomIndex,
+			stage: u8,
+			limit: u32,
+			cursor: Option<&[u8]>,
+		) -> Option<MultiRemovalResults> {
+			Some(match stage {
+				// before `ProposalOf`, which the cancelled spends are reported with
+				0 => Self::cancel_grace_periods(room_id, limit),
+				1 => <Voting<T, I>>::clear_prefix(room_id, limit, cursor),
+				2 => <ProposalOf<T, I>>::clear_prefix(room_id, limit, cursor),
+				3 => <ProposalsByHash<T, I>>::clear_prefix(room_id, limit, cursor),
+				4 => <MetadataOf<T, I>>::clear_prefix(room_id, limit, cursor),
+				5 => <ProposerOf<T, I>>::clear_prefix(room_id, limit, cursor),
+				6 => <QuadraticTally<T, I>>::clear_prefix(room_id, limit, cursor),
+				7 => <Escalations<T, I>>::clear_prefix(room_id, limit, cursor),
+				8 => <Blacklist<T, I>>::clear_prefix(room_id, limit, cursor),
+				9 => <SignedVoteNonce<T, I>>::clear_prefix(room_id, limit, cursor),
+				10 => <SpentVoiceCredits<T, I>>::clear_prefix(room_id, limit, cursor),
+				11 => <RecurringMotions<T, I>>::clear_prefix(room_id, limit, cursor),
+				12 => <EscalatedFrom<T, I>>::clear_prefix(room_id, limit, cursor),
+				13 => Self::leave_cross_rooms(room_id, limit),
+				_ => return None,
+			})
+		}
+
+		// Marks the rooms that reached their threshold as approved, then executes the motion once
+		// every room approved it or rejects it as soon as one room no longer can. Rooms that are
+		// being dissolved count as neither until they drop out of the motion.
+		fn update_cross_room(
+			index: ProposalIndex,
+			mut motion: CrossRoomMotion<T::AccountId, T::Proposal, T::BlockNumber>,
+		) -> DispatchResult {
+			for room_id in motion.rooms.clone() {
+				if motion.approved.iter().any(|(id, ..)| *id == room_id) ||
+					<DissolvingRooms<T, I>>::contains_key(room_id)
+				{
+					continue
+				}
+				let voting = Self::cross_room_voting(index, room_id)
+					.ok_or(Error::<T, I>::CrossRoomMissing)?;
+				let yes_votes = voting.ayes.len() as MemberCount;
+				let seats =
+					T::ListenHandler::get_room_council(room_id.into())?.len() as MemberCount;
+				let voted = voting.ayes.len() + voting.nays.len() + voting.abstentions.len();
+				let not_voted = seats.saturating_sub(voted as MemberCount);
+
+				if yes_votes >= voting.threshold {
+					motion.approved.push((room_id, yes_votes, seats));
+					Self::deposit_event(Event::CrossRoomApproved(index, room_id));
+				} else if yes_votes.saturating_add(not_voted) < voting.threshold {
+					Self::remove_cross_room(index, &motion.rooms);
+					Self::deposit_event(Event::CrossRoomRejected(index, Some(room_id)));
+					return Ok(())
+				}
+			}
+
+			if motion.approved.len() < motion.rooms.len() {
+				<CrossRoomMotions<T, I>>::insert(index, motion);
+				return Ok(())
+			}
+
+			Self::remove_cross_room(index, &motion.rooms);
+			let result = motion.proposal.dispatch(RoomRawOrigin::Rooms(motion.approved).into());
+			Self::deposit_event(Event::CrossRoomExecuted(
+				index,
+				result.map(|_| ()).map_err(|e| e.error),
+			));
+			Ok(())
+		}
+
+		fn remove_cross_room(index: ProposalIndex, rooms: &[RoomIndex]) {
+			<CrossRoomMotions<T, I>>::remove(index);
+			for room_id in rooms {
+				<CrossRoomVoting<T, I>>::remove(index, room_id);
+				<CrossRoomsOf<T, I>>::remove(room_id, index);
+			}
+		}
+
+		// Cancels at most `limit` of the approved spends of a dissolved room that wait for their
+		// grace period. The observer already saw their votes when they were approved.
+		fn cancel_grace_periods(room_id: RoomIndex, limit: u32) -> MultiRemovalResults {
+			let indexes: Vec<_> =
+				<GracePeriods<T, I>>::iter_key_prefix(room_id).take(limit as usize).collect();
+			for index in indexes.iter() {
+				<GracePeriods<T, I>>::remove(room_id, index);
+				if let Some(motion) = Self::proposal_of(room_id, index) {
+					Self::deposit_event(Event::Disapproved(Self::motion_hash(&motion)));
+				}
+			}
+
+			let loops = indexes.len() as u32;
+			// Entries are removed as they are visited, so the next call starts from the top.
+			let maybe_cursor =
+				<GracePeriods<T, I>>::iter_key_prefix(room_id).next().map(|_| Vec::new());
+			MultiRemovalResults { maybe_cursor, backend: loops, unique: loops, loops }
+		}
+
+		// Drops a dissolved room out of at most `limit` of the cross-room motions it takes part
+		// in. A motion left with a single room is rejected.
+		fn leave_cross_rooms(room_id: RoomIndex, limit: u32) -> MultiRemovalResults {
+			let indexes: Vec<_> =
+				<CrossRoomsOf<T, I>>::iter_key_prefix(room_id).take(limit as usize).collect();
+			for index in indexes.iter() {
+				<CrossRoomsOf<T, I>>::remove(room_id, index);
+				<CrossRoomVoting<T, I>>::remove(index, room_id);
+				let mut motion = match Self::cross_room_motions(index) {
+					Some(motion) => motion,
+					None => continue,
+				};
+				motion.rooms.retain(|id| *id != room_id);
+				motion.approved.retain(|(id, ..)| *id != room_id);
+				if motion.rooms.len() < 2 {
+					Self::remove_cross_room(*index, &motion.rooms);
+					Self::deposit_event(Event::CrossRoomRejected(*index, Some(room_id)));
+				} else {
+					// The other rooms may have approved it already. If one of them fails to
+					// report its council, the motion waits for the next vote instead.
+					<CrossRoomMotions<T, I>>::insert(index, motion.clone());
+					let _ = Self::update_cross_room(*index, motion);
+				}
+			}
+
+			let loops = indexes.len() as u32;
+			// Entries are removed as they are visited, so the next call starts from the top.
+			let maybe_cursor =
+				<CrossRoomsOf<T, I>>::iter_key_prefix(room_id).next().map(|_| Vec::new());
+			MultiRemovalResults { maybe_cursor, backend: loops, unique: loops, loops }
+		}
+
+		fn remove_hash_index(room_id: RoomIndex, index: ProposalIndex, proposal_hash: T::Hash) {
+			ProposalsByHash::<T, I>::mutate_exists(room_id, proposal_hash, |maybe_indexes| {
+				if let Some(indexes) = maybe_indexes {
+					indexes.retain(|i| i != &index);
+					if indexes.is_empty() {
+						*maybe_indexes = None;
+					}
+				}
+			});
+		}
 	}
 }
 
@@ -553,7 +2470,8 @@ impl<
 	type Success = (MemberCount, MemberCount);
 	fn try_origin(o: O) -> Result<Self::Success, O> {
 		o.into().and_then(|o| match o {
-			RoomRawOrigin::Members(n, m) if n >= N => Ok((n, m)),
+			RoomRawOrigin::Members(n, m) | RoomRawOrigin::RoomMembers(_, n, m) if n >= N =>
+				Ok((n, m)),
 			r => Err(O::from(r)),
 		})
 	}
@@ -564,6 +2482,30 @@ impl<
 	}
 }
 
+pub struct EnsureRooms<AccountId, I: 'static>(sp_std::marker::PhantomData<(AccountId, I)>);
+impl<
+		O: Into<Result<RoomRawOrigin<AccountId, I>, O>> + From<RoomRawOrigin<AccountId, I>>,
+		AccountId,
+		I,
+	> EnsureOrigin<O> for EnsureRooms<AccountId, I>
+{
+	type Success = Vec<RoomIndex>;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RoomRawOrigin::Rooms(rooms)
+				if !rooms.is_empty() &&
+					rooms.iter().all(|(_, ayes, seats)| ayes.saturating_mul(2) > *seats) =>
+				Ok(rooms.into_iter().map(|(room_id, ..)| room_id).collect()),
+			r => Err(O::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RoomRawOrigin::Rooms(vec![(Default::default(), 1, 1)]))
+	}
+}
+
 pub struct EnsureRoomRoot<T, AccountId, I: 'static>(sp_std::marker::PhantomData<(T, AccountId, I)>);
 
 impl<
@@ -605,7 +2547,8 @@ impl<
 	type Success = ();
 	fn try_origin(o: O) -> Result<Self::Success, O> {
 		o.into().and_then(|o| match o {
-			RoomRawOrigin::Members(n, m) if n * D > N * m => Ok(()),
+			RoomRawOrigin::Members(n, m) | RoomRawOrigin::RoomMembers(_, n, m) if n * D > N * m =>
+				Ok(()),
 			r => Err(O::from(r)),
 		})
 	}
@@ -616,6 +2559,32 @@ impl<
 	}
 }
 
+/// Like `EnsureProportionMoreThan`, for motions of a single room. Yields the room.
+pub struct EnsureRoomProportionMoreThan<AccountId, I: 'static, const N: u32, const D: u32>(
+	sp_std::marker::PhantomData<(AccountId, I)>,
+);
+impl<
+		O: Into<Result<RoomRawOrigin<AccountId, I>, O>> + From<RoomRawOrigin<AccountId, I>>,
+		AccountId,
+		I,
+		const N: u32,
+		const D: u32,
+	> EnsureOrigin<O> for EnsureRoomProportionMoreThan<AccountId, I, N, D>
+{
+	type Success = RoomIndex;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RoomRawOrigin::RoomMembers(room_id, n, m) if n * D > N * m => Ok(room_id),
+			r => Err(O::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RoomRawOrigin::RoomMembers(Default::default(), 1u32, 0u32))
+	}
+}
+
 pub struct EnsureProportionAtLeast<AccountId, I: 'static, const N: u32, const D: u32>(
 	sp_std::marker::PhantomData<(AccountId, I)>,
 );
@@ -630,7 +2599,9 @@ impl<
 	type Success = ();
 	fn try_origin(o: O) -> Result<Self::Success, O> {
 		o.into().and_then(|o| match o {
-			RoomRawOrigin::Members(n, m) if n * D >= N * m => Ok(()),
+			RoomRawOrigin::Members(n, m) | RoomRawOrigin::RoomMembers(_, n, m)
+				if n * D >= N * m =>
+				Ok(()),
 			r => Err(O::from(r)),
 		})
 	}
@@ -644,15 +2615,71 @@ impl<
 impl<T: Config<I>, I: 'static> CollectiveHandler<u64, T::BlockNumber, DispatchError>
 	for Pallet<T, I>
 {
-	fn remove_room_collective_info(room_id: u64) -> result::Result<(), DispatchError> {
-		<ProposalCount<T, I>>::remove(room_id);
-		<Voting<T, I>>::remove_prefix(room_id, None);
-		<ProposalOf<T, I>>::remove_prefix(room_id, None);
-		<Proposals<T, I>>::remove(room_id);
-		Ok(())
+	/// Close every open motion of the room and start removing its storage.
+	///
+	/// Up to `DissolutionLimit` items are removed right away and as many in every following
+	/// block until `RoomDissolved` is emitted. Returns the weight consumed so far. Motions carry
+	/// no deposit, so there is nothing to refund.
+	fn remove_room_collective_info(room_id: u64) -> result::Result<Weight, DispatchError> {
+		let open = <Proposals<T, I>>::take(room_id);
+		let mut weight = T::DbWeight::get().reads_writes(2 * open.len() as u64 + 1, 2);
+		for index in open {
+			if let Some(motion) = Self::proposal_of(room_id, index) {
+				Self::deposit_event(Event::Disapproved(Self::motion_hash(&motion)));
+				if let Some(voting) = Self::voting(room_id, index) {
+					Self::notify_closed(room_id, index, MotionOutcome::Overruled, &voting);
+				}
+			}
+		}
+		<DissolvingRooms<T, I>>::insert(room_id, (0, None::<Vec<u8>>));
+		weight =
+			weight.saturating_add(Self::continue_dissolution(room_id, T::DissolutionLimit::get()));
+		Ok(weight)
 	}
 
 	fn get_motion_duration(_room_id: u64) -> T::BlockNumber {
 		T::MotionDuration::get()
 	}
 }
+
+impl<T: Config<I>, I: 'static>
+	EscalationHandler<RoomIndex, RoomMotion<<T as Config<I>>::Proposal>, DispatchError>
+	for Pallet<T, I>
+{
+	fn escalate(
+		room_id: RoomIndex,
+		source_index: ProposalIndex,
+		motion: RoomMotion<<T as Config<I>>::Proposal>,
+		threshold: MemberCount,
+	) -> result::Result<ProposalIndex, DispatchError> {
+		ensure!(!<DissolvingRooms<T, I>>::contains_key(room_id), Error::<T, I>::RoomDissolving);
+		ensure!(
+			motion
+				.calls()
+				.iter()
+				.all(|call| <T as Config<I>>::BaseCallFilter::contains(call)),
+			Error::<T, I>::DisallowFunc
+		);
+		let proposal_hash = Self::motion_hash(&motion);
+		Self::ensure_not_blacklisted(room_id, proposal_hash)?;
+
+		// Nobody has voted in this collective yet, so even a threshold of one waits for a vote.
+		let index = Self::insert_motion(
+			None,
+			room_id,
+			threshold.max(1),
+			proposal_hash,
+			motion,
+			Default::default(),
+			vec![],
+		)?;
+		<EscalatedFrom<T, I>>::insert(room_id, index, source_index);
+		Self::deposit_event(Event::ProposedByEscalation(
+			room_id,
+			index,
+			proposal_hash,
+			source_index,
+		));
+		Ok(index)
+	}
+}
@@ -0,0 +1,247 @@
+// Copyright 2021 LISTEN Developer.
+// This file is part of LISTEN.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the dao pallet.
+//!
+//! Every step reads and writes the value layouts of its own storage version, declared next to
+//! it, so that a released step never changes. A later storage change gets a new step.
+//!
+//! `GracePeriods` and `GraceQueue` were added with the layouts of storage version 4 and are left
+//! alone.
+
+use super::*;
+use frame_support::{
+	storage::{migration::storage_iter, storage_prefix, unhashed},
+	traits::{GetStorageVersion, StorageVersion},
+	Blake2_128Concat, ReversibleStorageHasher,
+};
+use sp_std::collections::btree_map::BTreeMap;
+
+/// Run every migration the on-chain storage version has not seen yet.
+pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+	let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+	let mut weight = T::DbWeight::get().reads(1);
+
+	if on_chain_version < 1 {
+		weight = weight.saturating_add(v1::migrate::<T, I>());
+	}
+	if on_chain_version < 2 {
+		weight = weight.saturating_add(v2::migrate::<T, I>());
+	}
+	if on_chain_version < 3 {
+		weight = weight.saturating_add(v3::migrate::<T, I>());
+	}
+	if on_chain_version < 4 {
+		weight = weight.saturating_add(v4::migrate::<T, I>());
+	}
+
+	weight
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// Wrap every call stored in `ProposalOf` into `RoomMotion::Single`.
+	pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+		let mut translated = 0u64;
+		ProposalOf::<T, I>::translate_values::<<T as Config<I>>::Proposal, _>(|proposal| {
+			translated += 1;
+			Some(RoomMotion::Single(proposal))
+		});
+
+		StorageVersion::new(1).put::<Pallet<T, I>>();
+		T::DbWeight::get().reads_writes(translated, translated + 1)
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// `ListenDaoVotes` up to storage version 2, with an unbounded reason.
+	#[derive(Encode, Decode)]
+	pub struct ListenDaoVotesV2<AccountId, BlockNumber> {
+		pub(super) index: ProposalIndex,
+		pub(super) reason: Option<Vec<u8>>,
+		pub(super) threshold: MemberCount,
+		pub(super) ayes: Vec<AccountId>,
+		pub(super) nays: Vec<AccountId>,
+		pub(super) end: BlockNumber,
+	}
+
+	/// Re-key `ProposalOf` and `Voting` from `(room_id, proposal_hash)` to
+	/// `(room_id, proposal_index)`, fill `ProposalsByHash` and store indexes in `Proposals`.
+	///
+	/// The index of a motion is the one recorded in its votes. Calls without votes can not be
+	/// reached by any extrinsic and are dropped.
+	pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+		let votings =
+			take_hash_keyed::<T, I, ListenDaoVotesV2<T::AccountId, T::BlockNumber>>(b"Voting");
+		let motions =
+			take_hash_keyed::<T, I, RoomMotion<<T as Config<I>>::Proposal>>(b"ProposalOf");
+		let mut reads = (votings.len() + motions.len()) as u64;
+		let mut writes = reads;
+
+		let mut index_of = BTreeMap::new();
+		for (room_id, proposal_hash, voting) in votings {
+			index_of.insert((room_id, proposal_hash), voting.index);
+			unhashed::put(&Voting::<T, I>::hashed_key_for(room_id, voting.index), &voting);
+		}
+		for (room_id, proposal_hash, motion) in motions {
+			if let Some(&index) = index_of.get(&(room_id, proposal_hash)) {
+				ProposalOf::<T, I>::insert(room_id, index, motion);
+				ProposalsByHash::<T, I>::append(room_id, proposal_hash, index);
+				writes += 2;
+			}
+		}
+
+		Proposals::<T, I>::translate::<Vec<T::Hash>, _>(|room_id, hashes| {
+			reads += 1;
+			writes += 1;
+			Some(
+				hashes
+					.into_iter()
+					.filter_map(|hash| index_of.get(&(room_id, hash)).copied())
+					.collect(),
+			)
+		});
+
+		StorageVersion::new(2).put::<Pallet<T, I>>();
+		T::DbWeight::get().reads_writes(reads, writes + 1)
+	}
+
+	// Removes every entry of a double map keyed by `(RoomIndex, T::Hash)` and returns them.
+	fn take_hash_keyed<T: Config<I>, I: 'static, V: Decode>(
+		item: &[u8],
+	) -> Vec<(RoomIndex, T::Hash, V)> {
+		let pallet = <Pallet<T, I> as PalletInfoAccess>::name();
+		storage_iter::<V>(pallet.as_bytes(), item)
+			.drain()
+			.filter_map(|(key, value)| {
+				let mut key = Blake2_128Concat::reverse(&key);
+				let room_id = RoomIndex::decode(&mut key).ok()?;
+				let mut key = Blake2_128Concat::reverse(key);
+				let proposal_hash = T::Hash::decode(&mut key).ok()?;
+				Some((room_id, proposal_hash, value))
+			})
+			.collect()
+	}
+}
+
+pub mod v3 {
+	use super::{v2::ListenDaoVotesV2, *};
+
+	/// `ListenDaoVotes` in storage version 3, without abstentions.
+	#[derive(Encode, Decode)]
+	pub struct ListenDaoVotesV3<AccountId, BlockNumber> {
+		pub(super) index: ProposalIndex,
+		pub(super) threshold: MemberCount,
+		pub(super) ayes: Vec<AccountId>,
+		pub(super) nays: Vec<AccountId>,
+		pub(super) end: BlockNumber,
+	}
+
+	/// Drop the reason from `Voting` and keep it, truncated, as the title of
+	/// the motion in `MetadataOf`.
+	///
+	/// Proposers of existing motions are unknown, so those motions can not be amended.
+	pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+		let translated = translate_indexed::<T, I, ListenDaoVotesV2<T::AccountId, T::BlockNumber>, _>(
+			b"Voting",
+			|room_id, index, old| migrate_votes::<T, I>(room_id, index, old),
+		);
+
+		StorageVersion::new(3).put::<Pallet<T, I>>();
+		T::DbWeight::get().reads_writes(translated, translated * 2 + 1)
+	}
+
+	fn migrate_votes<T: Config<I>, I: 'static>(
+		room_id: RoomIndex,
+		index: ProposalIndex,
+		old: ListenDaoVotesV2<T::AccountId, T::BlockNumber>,
+	) -> ListenDaoVotesV3<T::AccountId, T::BlockNumber> {
+		if let Some(mut reason) = old.reason {
+			reason.truncate(T::MaxTitleLen::get() as usize);
+			let metadata = MotionMetadata::<T, I> {
+				title: BoundedVec::try_from(reason).unwrap_or_default(),
+				..Default::default()
+			};
+			MetadataOf::<T, I>::insert(room_id, index, metadata);
+		}
+		ListenDaoVotesV3 {
+			index: old.index,
+			threshold: old.threshold,
+			ayes: old.ayes,
+			nays: old.nays,
+			end: old.end,
+		}
+	}
+
+	// Rewrites every value of a double map keyed by `(RoomIndex, ProposalIndex)`, whatever type
+	// the pallet currently declares for it.
+	fn translate_indexed<T: Config<I>, I: 'static, O: Decode, V: Encode>(
+		item: &[u8],
+		mut f: impl FnMut(RoomIndex, ProposalIndex, O) -> V,
+	) -> u64 {
+		let pallet = <Pallet<T, I> as PalletInfoAccess>::name();
+		let prefix = storage_prefix(pallet.as_bytes(), item);
+		let mut translated = 0u64;
+		for (key, old) in storage_iter::<O>(pallet.as_bytes(), item) {
+			let mut raw_key = Blake2_128Concat::reverse(&key);
+			let room_id = match RoomIndex::decode(&mut raw_key) {
+				Ok(room_id) => room_id,
+				Err(_) => continue,
+			};
+			let mut raw_key = Blake2_128Concat::reverse(raw_key);
+			let index = match ProposalIndex::decode(&mut raw_key) {
+				Ok(index) => index,
+				Err(_) => continue,
+			};
+			unhashed::put(&[&prefix[..], &key[..]].concat(), &f(room_id, index, old));
+			translated += 1;
+		}
+		translated
+	}
+}
+
+pub mod v4 {
+	use super::{v3::ListenDaoVotesV3, *};
+
+	/// Add an empty set of abstentions to `Voting`.
+	pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+		let mut translated = 0u64;
+		Voting::<T, I>::translate::<ListenDaoVotesV3<T::AccountId, T::BlockNumber>, _>(
+			|_, _, old| {
+				translated += 1;
+				Some(migrate_votes(old))
+			},
+		);
+
+		StorageVersion::new(4).put::<Pallet<T, I>>();
+		T::DbWeight::get().reads_writes(translated, translated + 1)
+	}
+
+	fn migrate_votes<AccountId, BlockNumber>(
+		old: ListenDaoVotesV3<AccountId, BlockNumber>,
+	) -> ListenDaoVotes<AccountId, BlockNumber> {
+		ListenDaoVotes {
+			index: old.index,
+			threshold: old.threshold,
+			ayes: old.ayes,
+			nays: old.nays,
+			abstentions: vec![],
+			end: old.end,
+		}
+	}
+}
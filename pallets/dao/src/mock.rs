@@ -0,0 +1,268 @@
+//! Mocks for the dao module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, parameter_types, traits::Everything};
+use sp_core::H256;
+use sp_runtime::{
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::IdentityLookup,
+};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
+
+use crate as dao;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u64;
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+thread_local! {
+	pub static COUNCILS: RefCell<BTreeMap<RoomIndex, Vec<AccountId>>> = RefCell::new(BTreeMap::new());
+	pub static OUTCOMES: RefCell<Vec<(Option<AccountId>, MotionOutcome)>> = RefCell::new(vec![]);
+}
+
+pub struct ListenHandlerMock;
+impl ListenHandler<RoomIndex, AccountId, DispatchError, u128> for ListenHandlerMock {
+	fn get_room_council(room_id: RoomIndex) -> result::Result<Vec<AccountId>, DispatchError> {
+		COUNCILS
+			.with(|c| c.borrow().get(&room_id).cloned())
+			.ok_or(DispatchError::Other("room does not exist"))
+	}
+
+	fn get_prime(room_id: RoomIndex) -> result::Result<Option<AccountId>, DispatchError> {
+		Self::get_room_council(room_id).map(|council| council.last().cloned())
+	}
+
+	fn get_root(room_id: RoomIndex) -> result::Result<AccountId, DispatchError> {
+		Self::get_room_council(room_id)?
+			.first()
+			.cloned()
+			.ok_or(DispatchError::Other("room has no root"))
+	}
+
+	fn get_room_free_amount(_room_id: RoomIndex) -> u128 {
+		ROOM_FUNDS
+	}
+
+	fn sub_room_free_amount(
+		_room_id: RoomIndex,
+		_amount: u128,
+	) -> result::Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn add_room_free_amount(
+		_room_id: RoomIndex,
+		_amount: u128,
+	) -> result::Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn exit_room_council(
+		room_id: RoomIndex,
+		who: AccountId,
+		_payout: u128,
+	) -> result::Result<(), DispatchError> {
+		COUNCILS.with(|c| {
+			if let Some(council) = c.borrow_mut().get_mut(&room_id) {
+				council.retain(|a| a != &who);
+			}
+		});
+		Ok(())
+	}
+
+	fn is_in_room(room_id: RoomIndex, who: AccountId) -> result::Result<bool, DispatchError> {
+		Self::get_room_council(room_id).map(|council| council.contains(&who))
+	}
+
+	fn set_room_council(
+		room_id: RoomIndex,
+		council: Vec<AccountId>,
+	) -> result::Result<(), DispatchError> {
+		COUNCILS.with(|c| c.borrow_mut().insert(room_id, council));
+		Ok(())
+	}
+}
+
+pub struct PendingSpendsMock;
+impl PendingSpends<RoomIndex, u128> for PendingSpendsMock {
+	fn pending_spends(_room_id: RoomIndex) -> u128 {
+		PENDING_SPENDS
+	}
+}
+
+/// Records the proposer and the outcome of every closed motion in `OUTCOMES`.
+pub struct ObserverMock;
+impl GovernanceObserver<RoomIndex, AccountId> for ObserverMock {
+	fn on_motion_closed(
+		_room_id: RoomIndex,
+		proposer: Option<AccountId>,
+		outcome: MotionOutcome,
+		_ayes: &[AccountId],
+		_nays: &[AccountId],
+		_abstentions: &[AccountId],
+	) {
+		OUTCOMES.with(|o| o.borrow_mut().push((proposer, outcome)));
+	}
+}
+
+pub fn closed_outcomes() -> Vec<(Option<AccountId>, MotionOutcome)> {
+	OUTCOMES.with(|o| o.borrow().clone())
+}
+
+/// Members weigh as much as their account id in rooms that weigh votes.
+pub struct VoteWeightMock;
+impl VoteWeightSource<RoomIndex, AccountId> for VoteWeightMock {
+	fn vote_weight(_room_id: RoomIndex, who: &AccountId) -> u64 {
+		*who
+	}
+}
+
+/// Plain remarks stand in for the calls that spend the room's funds.
+pub struct SpendCallsMock;
+impl Contains<Call> for SpendCallsMock {
+	fn contains(call: &Call) -> bool {
+		matches!(call, Call::System(frame_system::Call::remark { .. }))
+	}
+}
+
+parameter_types! {
+	pub const MotionDuration: u64 = 5;
+	pub const MaxProposals: u32 = 3;
+	pub const FastTrackThreshold: Permill = Permill::from_percent(60);
+	pub const VetoCoolOff: u64 = 10;
+	pub const MaxSignedVotes: u32 = 5;
+	pub const GracePeriod: u64 = 3;
+	pub const MaxGraceExecutions: u32 = 2;
+	pub const MaxBatchCalls: u32 = 4;
+	pub const VoiceCredits: u32 = 9;
+	pub const MaxTitleLen: u32 = 16;
+	pub const MaxLinkLen: u32 = 32;
+	pub const MaxRationaleLen: u32 = 32;
+	pub const MaxCrossRooms: u32 = 3;
+	pub const MaxRecurringMotions: u32 = 2;
+	pub const DissolutionLimit: u32 = 2;
+}
+
+impl Config for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type DefaultVote = PrimeDefaultVote;
+	type WeightInfo = ();
+	type ListenHandler = ListenHandlerMock;
+	type BaseCallFilter = Everything;
+	type MotionDuration = MotionDuration;
+	type MaxProposals = MaxProposals;
+	type FastTrackThreshold = FastTrackThreshold;
+	type VetoCoolOff = VetoCoolOff;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	type MaxSignedVotes = MaxSignedVotes;
+	type SpendCalls = SpendCallsMock;
+	type GracePeriod = GracePeriod;
+	type MaxGraceExecutions = MaxGraceExecutions;
+	type PendingSpends = PendingSpendsMock;
+	type MaxBatchCalls = MaxBatchCalls;
+	type VoiceCredits = VoiceCredits;
+	type Escalation = ();
+	type MaxTitleLen = MaxTitleLen;
+	type MaxLinkLen = MaxLinkLen;
+	type MaxRationaleLen = MaxRationaleLen;
+	type Observer = ObserverMock;
+	type VoteWeight = VoteWeightMock;
+	type MaxCrossRooms = MaxCrossRooms;
+	type MaxRecurringMotions = MaxRecurringMotions;
+	type DissolutionLimit = DissolutionLimit;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Dao: dao::{Pallet, Call, Storage, Origin<T>, Event<T>},
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+
+pub const ROOM: RoomIndex = 1;
+pub const OTHER_ROOM: RoomIndex = 2;
+
+/// The free amount of every room, of which `PENDING_SPENDS` is committed to approved spends.
+pub const ROOM_FUNDS: u128 = 90;
+pub const PENDING_SPENDS: u128 = 30;
+
+pub fn make_proposal(value: u64) -> Call {
+	Call::System(frame_system::Call::remark_with_event { remark: value.encode() })
+}
+
+pub fn make_spend_proposal(value: u64) -> Call {
+	Call::System(frame_system::Call::remark { remark: value.encode() })
+}
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build() -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		COUNCILS.with(|c| {
+			*c.borrow_mut() = BTreeMap::from([
+				(ROOM, vec![ALICE, BOB, CHARLIE]),
+				(OTHER_ROOM, vec![CHARLIE, DAVE]),
+			])
+		});
+		OUTCOMES.with(|o| o.borrow_mut().clear());
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	/// Run the test and check the storage invariants afterwards.
+	pub fn build_and_execute(test: impl FnOnce()) {
+		Self::build().execute_with(|| {
+			test();
+			Dao::do_try_state().unwrap();
+		})
+	}
+}
@@ -0,0 +1,1165 @@
+//! Unit tests for the dao module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use mock::{Call, Event, Origin, *};
+use sp_core::H256;
+use sp_runtime::{testing::TestSignature, traits::BlakeTwo256};
+
+fn propose(who: AccountId, threshold: MemberCount, value: u64) -> (ProposalIndex, H256) {
+	let proposal = make_proposal(value);
+	let hash = BlakeTwo256::hash_of(&proposal);
+	let len = proposal.using_encoded(|p| p.len()) as u32;
+	let index = Dao::proposal_count(ROOM);
+	assert_ok!(Dao::propose(
+		Origin::signed(who),
+		ROOM,
+		threshold,
+		Box::new(proposal),
+		Default::default(),
+		len
+	));
+	(index, hash)
+}
+
+#[test]
+fn propose_works() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+
+		assert_eq!(Dao::proposals(ROOM), vec![index]);
+		assert_eq!(Dao::proposal_of(ROOM, index), Some(RoomMotion::Single(make_proposal(42))));
+		assert_eq!(Dao::proposals_by_hash(ROOM, hash), vec![index]);
+		assert_eq!(Dao::proposal_count(ROOM), 1);
+		System::assert_last_event(Event::Dao(crate::Event::Proposed(ALICE, index, hash, 3)));
+	});
+}
+
+#[test]
+fn propose_requires_membership() {
+	ExtBuilder::build_and_execute(|| {
+		let proposal = make_proposal(42);
+		let len = proposal.using_encoded(|p| p.len()) as u32;
+		assert_noop!(
+			Dao::propose(
+				Origin::signed(DAVE),
+				ROOM,
+				2,
+				Box::new(proposal),
+				Default::default(),
+				len
+			),
+			Error::<Runtime>::NotMember
+		);
+	});
+}
+
+#[test]
+fn identical_calls_get_their_own_index() {
+	ExtBuilder::build_and_execute(|| {
+		let (first, hash) = propose(ALICE, 3, 42);
+		let (second, _) = propose(BOB, 3, 42);
+
+		assert_ne!(first, second);
+		assert_eq!(Dao::proposals(ROOM), vec![first, second]);
+		assert_eq!(Dao::proposals_by_hash(ROOM, hash), vec![first, second]);
+	});
+}
+
+#[test]
+fn limit_active_proposals() {
+	ExtBuilder::build_and_execute(|| {
+		for value in 0..MaxProposals::get() as u64 {
+			propose(ALICE, 3, value);
+		}
+		let proposal = make_proposal(99);
+		let len = proposal.using_encoded(|p| p.len()) as u32;
+		assert_noop!(
+			Dao::propose(
+				Origin::signed(ALICE),
+				ROOM,
+				3,
+				Box::new(proposal),
+				Default::default(),
+				len
+			),
+			Error::<Runtime>::TooManyProposals
+		);
+	});
+}
+
+#[test]
+fn prime_can_veto_again_after_cool_off() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+		assert_noop!(
+			Dao::veto(Origin::signed(ALICE), ROOM, hash, index),
+			Error::<Runtime>::NotPrime
+		);
+		assert_ok!(Dao::veto(Origin::signed(CHARLIE), ROOM, hash, index));
+		System::assert_last_event(Event::Dao(crate::Event::Vetoed(CHARLIE, hash, 11)));
+
+		let proposal = make_proposal(42);
+		assert_noop!(
+			Dao::propose(
+				Origin::signed(ALICE),
+				ROOM,
+				3,
+				Box::new(proposal),
+				Default::default(),
+				100
+			),
+			Error::<Runtime>::ProposalBlacklisted
+		);
+
+		System::set_block_number(1 + VetoCoolOff::get());
+		let (index, hash) = propose(ALICE, 3, 42);
+		assert_eq!(Dao::blacklist(ROOM, hash), None);
+		assert_ok!(Dao::veto(Origin::signed(CHARLIE), ROOM, hash, index));
+		assert_eq!(Dao::blacklist(ROOM, hash), Some((21, vec![CHARLIE])));
+	});
+}
+
+#[test]
+fn vote_closes_approved_motion() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 2, 42);
+
+		assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Aye, None));
+
+		assert!(Dao::proposals(ROOM).is_empty());
+		assert_eq!(Dao::proposal_of(ROOM, index), None);
+		assert_eq!(Dao::voting(ROOM, index), None);
+		assert!(Dao::proposals_by_hash(ROOM, hash).is_empty());
+		System::assert_has_event(Event::Dao(crate::Event::Approved(hash)));
+		System::assert_last_event(Event::Dao(crate::Event::Closed(hash, 2, 0)));
+	});
+}
+
+#[test]
+fn vote_closes_disapproved_motion() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+
+		assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Nay, None));
+
+		assert!(Dao::proposals(ROOM).is_empty());
+		System::assert_has_event(Event::Dao(crate::Event::Disapproved(hash)));
+		assert_eq!(closed_outcomes(), vec![(Some(ALICE), MotionOutcome::Rejected)]);
+	});
+}
+
+#[test]
+fn vetoed_and_root_disapproved_motions_are_overruled() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+		assert_ok!(Dao::veto(Origin::signed(CHARLIE), ROOM, hash, index));
+
+		let (_, hash) = propose(BOB, 3, 43);
+		assert_ok!(Dao::disapprove_proposal(Origin::root(), ROOM, hash));
+
+		assert_eq!(
+			closed_outcomes(),
+			vec![(Some(ALICE), MotionOutcome::Overruled), (Some(BOB), MotionOutcome::Overruled)]
+		);
+	});
+}
+
+#[test]
+fn fast_track_needs_root_or_prime_and_a_supermajority() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+
+		assert_noop!(
+			Dao::fast_track(Origin::signed(BOB), ROOM, hash, index),
+			Error::<Runtime>::NotRootOrPrime
+		);
+		// one aye of three seats is below the 60% threshold
+		assert_noop!(
+			Dao::fast_track(Origin::signed(ALICE), ROOM, hash, index),
+			Error::<Runtime>::NotSupermajority
+		);
+
+		assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Aye, None));
+		assert_eq!(Dao::proposals(ROOM), vec![index]);
+		assert_ok!(Dao::fast_track(Origin::signed(CHARLIE), ROOM, hash, index));
+
+		assert!(Dao::proposals(ROOM).is_empty());
+		System::assert_has_event(Event::Dao(crate::Event::FastTracked(CHARLIE, hash, 2)));
+		System::assert_has_event(Event::Dao(crate::Event::Approved(hash)));
+		System::assert_last_event(Event::Dao(crate::Event::Closed(hash, 2, 0)));
+	});
+}
+
+fn sign_vote(
+	who: AccountId,
+	genesis_hash: H256,
+	room_id: RoomIndex,
+	hash: H256,
+	index: ProposalIndex,
+	approve: bool,
+	nonce: u64,
+) -> TestSignature {
+	let payload =
+		(SIGNED_VOTE_CONTEXT, &b"Dao"[..], genesis_hash, room_id, hash, index, approve, nonce)
+			.encode();
+	TestSignature(who, payload)
+}
+
+fn ballot(
+	who: AccountId,
+	hash: H256,
+	index: ProposalIndex,
+	approve: bool,
+) -> (AccountId, bool, TestSignature) {
+	let nonce = Dao::signed_vote_nonce(ROOM, who);
+	(who, approve, sign_vote(who, System::block_hash(0), ROOM, hash, index, approve, nonce))
+}
+
+fn submit(
+	hash: H256,
+	index: ProposalIndex,
+	votes: Vec<(AccountId, bool, TestSignature)>,
+) -> DispatchResult {
+	Dao::submit_signed_votes(Origin::signed(DAVE), ROOM, hash, index, votes.try_into().unwrap())
+}
+
+#[test]
+fn relayed_signed_votes_are_counted() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+
+		assert_ok!(submit(hash, index, vec![ballot(BOB, hash, index, true)]));
+		System::assert_last_event(Event::Dao(crate::Event::Voted(
+			BOB,
+			hash,
+			Ballot::Aye,
+			3,
+			2,
+			0,
+			0,
+			None,
+		)));
+		assert_eq!(Dao::voting(ROOM, index).unwrap().ayes, vec![ALICE, BOB]);
+		assert_eq!(Dao::signed_vote_nonce(ROOM, BOB), 1);
+
+		assert_ok!(submit(hash, index, vec![ballot(CHARLIE, hash, index, true)]));
+		System::assert_has_event(Event::Dao(crate::Event::Approved(hash)));
+	});
+}
+
+#[test]
+fn relayed_ballots_must_be_signed_for_this_chain_room_and_nonce() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+		let genesis_hash = System::block_hash(0);
+
+		// signed by someone else
+		let signature = sign_vote(CHARLIE, genesis_hash, ROOM, hash, index, true, 0);
+		assert_noop!(
+			submit(hash, index, vec![(BOB, true, signature)]),
+			Error::<Runtime>::InvalidSignature
+		);
+		// for another chain
+		let signature = sign_vote(BOB, H256::zero(), ROOM, hash, index, true, 0);
+		assert_noop!(
+			submit(hash, index, vec![(BOB, true, signature)]),
+			Error::<Runtime>::InvalidSignature
+		);
+		// for another room
+		let signature = sign_vote(BOB, genesis_hash, OTHER_ROOM, hash, index, true, 0);
+		assert_noop!(
+			submit(hash, index, vec![(BOB, true, signature)]),
+			Error::<Runtime>::InvalidSignature
+		);
+		// by someone outside the council
+		assert_noop!(
+			submit(hash, index, vec![ballot(DAVE, hash, index, true)]),
+			Error::<Runtime>::NotMember
+		);
+
+		// a ballot can not be replayed once its nonce is used
+		let replayed = ballot(BOB, hash, index, true);
+		assert_ok!(submit(hash, index, vec![replayed.clone()]));
+		assert_noop!(submit(hash, index, vec![replayed]), Error::<Runtime>::InvalidSignature);
+	});
+}
+
+#[test]
+fn repeated_relayed_ballots_are_skipped() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+		assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Aye, None));
+
+		assert_ok!(submit(
+			hash,
+			index,
+			vec![ballot(BOB, hash, index, true), ballot(CHARLIE, hash, index, true)]
+		));
+		System::assert_has_event(Event::Dao(crate::Event::SignedVoteSkipped(BOB, hash)));
+		System::assert_has_event(Event::Dao(crate::Event::Approved(hash)));
+		assert_eq!(Dao::signed_vote_nonce(ROOM, BOB), 1);
+		assert_eq!(Dao::signed_vote_nonce(ROOM, CHARLIE), 1);
+	});
+}
+
+#[test]
+fn vote_by_index_works() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+
+		assert_ok!(Dao::vote_by_index(Origin::signed(BOB), ROOM, index, Ballot::Aye, None));
+
+		System::assert_last_event(Event::Dao(crate::Event::Voted(
+			BOB,
+			hash,
+			Ballot::Aye,
+			3,
+			2,
+			0,
+			0,
+			None,
+		)));
+	});
+}
+
+#[test]
+fn changing_vote_moves_voter() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+
+		assert_ok!(Dao::vote(Origin::signed(ALICE), ROOM, hash, index, Ballot::Nay, None));
+		let voting = Dao::voting(ROOM, index).unwrap();
+		assert!(voting.ayes.is_empty());
+		assert_eq!(voting.nays, vec![ALICE]);
+
+		assert_noop!(
+			Dao::vote(Origin::signed(ALICE), ROOM, hash, index, Ballot::Nay, None),
+			Error::<Runtime>::DuplicateVote
+		);
+	});
+}
+
+#[test]
+fn vote_checks_proposal_hash() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, _) = propose(ALICE, 3, 42);
+		let other = BlakeTwo256::hash_of(&make_proposal(43));
+
+		assert_noop!(
+			Dao::vote(Origin::signed(BOB), ROOM, other, index, Ballot::Aye, None),
+			Error::<Runtime>::WrongIndex
+		);
+	});
+}
+
+#[test]
+fn try_state_detects_inconsistent_votes() {
+	ExtBuilder::build().execute_with(|| {
+		let (index, _) = propose(ALICE, 3, 42);
+		assert_ok!(Dao::do_try_state());
+
+		Voting::<Runtime>::mutate(ROOM, index, |voting| {
+			voting.as_mut().unwrap().nays.push(ALICE);
+		});
+		assert!(Dao::do_try_state().is_err());
+	});
+}
+
+#[test]
+fn try_state_detects_missing_motion() {
+	ExtBuilder::build().execute_with(|| {
+		let (index, _) = propose(ALICE, 3, 42);
+
+		ProposalOf::<Runtime>::remove(ROOM, index);
+		assert!(Dao::do_try_state().is_err());
+	});
+}
+
+#[test]
+fn try_state_detects_index_beyond_count() {
+	ExtBuilder::build().execute_with(|| {
+		propose(ALICE, 3, 42);
+
+		ProposalCount::<Runtime>::insert(ROOM, 0);
+		assert!(Dao::do_try_state().is_err());
+	});
+}
+
+#[test]
+fn try_state_detects_grace_period_without_motion() {
+	ExtBuilder::build().execute_with(|| {
+		let (index, hash) = propose_spend(ALICE, 1);
+		assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Aye, None));
+		assert!(Dao::grace_periods(ROOM, index).is_some());
+		assert_ok!(Dao::do_try_state());
+
+		ProposalOf::<Runtime>::remove(ROOM, index);
+		assert!(Dao::do_try_state().is_err());
+	});
+}
+
+#[test]
+fn only_root_sets_voting_mode_without_open_motions() {
+	ExtBuilder::build_and_execute(|| {
+		assert_noop!(
+			Dao::set_voting_mode(Origin::signed(BOB), ROOM, VotingMode::Quadratic),
+			Error::<Runtime>::NotRoomOwner
+		);
+		propose(ALICE, 3, 42);
+		assert_noop!(
+			Dao::set_voting_mode(Origin::signed(ALICE), ROOM, VotingMode::Quadratic),
+			Error::<Runtime>::MotionsOpen
+		);
+	});
+}
+
+#[test]
+fn quadratic_votes_cost_voice_credits() {
+	ExtBuilder::build_and_execute(|| {
+		assert_ok!(Dao::set_voting_mode(Origin::signed(ALICE), ROOM, VotingMode::Quadratic));
+		let (first, first_hash) = propose(ALICE, 7, 42);
+		let (second, second_hash) = propose(ALICE, 7, 43);
+
+		assert_noop!(
+			Dao::vote(Origin::signed(BOB), ROOM, first_hash, first, Ballot::Aye, None),
+			Error::<Runtime>::WrongVotingMode
+		);
+
+		assert_ok!(Dao::vote_quadratic(Origin::signed(BOB), ROOM, first_hash, first, true, 2));
+		assert_eq!(Dao::quadratic_tally(ROOM, first), (2, 0));
+		assert_eq!(Dao::spent_voice_credits(ROOM, BOB), (0, 4));
+		System::assert_last_event(Event::Dao(crate::Event::QuadraticVoted(
+			BOB, first_hash, true, 2, 2, 0,
+		)));
+
+		assert_noop!(
+			Dao::vote_quadratic(Origin::signed(BOB), ROOM, first_hash, first, false, 1),
+			Error::<Runtime>::DuplicateVote
+		);
+		assert_noop!(
+			Dao::vote_quadratic(Origin::signed(CHARLIE), ROOM, first_hash, first, true, 0),
+			Error::<Runtime>::ZeroVotes
+		);
+		assert_noop!(
+			Dao::vote_quadratic(Origin::signed(BOB), ROOM, second_hash, second, true, 3),
+			Error::<Runtime>::InsufficientVoiceCredits
+		);
+		assert_ok!(Dao::vote_quadratic(Origin::signed(BOB), ROOM, second_hash, second, true, 2));
+
+		// Credits replenish every `MotionDuration`.
+		System::set_block_number(MotionDuration::get());
+		let (third, third_hash) = propose(ALICE, 7, 44);
+		assert_ok!(Dao::vote_quadratic(Origin::signed(BOB), ROOM, third_hash, third, true, 3));
+	});
+}
+
+#[test]
+fn weighted_votes_count_vote_weight() {
+	ExtBuilder::build_and_execute(|| {
+		// Members weigh as much as their account id: ALICE 1, BOB 2, CHARLIE 3.
+		assert_ok!(Dao::set_voting_mode(Origin::signed(ALICE), ROOM, VotingMode::Weighted));
+		let (first, first_hash) = propose(BOB, 4, 42);
+
+		assert_ok!(Dao::vote(Origin::signed(ALICE), ROOM, first_hash, first, Ballot::Aye, None));
+		System::assert_last_event(Event::Dao(crate::Event::Voted(
+			ALICE,
+			first_hash,
+			Ballot::Aye,
+			3,
+			3,
+			0,
+			0,
+			None,
+		)));
+		assert_ok!(Dao::vote(Origin::signed(CHARLIE), ROOM, first_hash, first, Ballot::Nay, None));
+		System::assert_last_event(Event::Dao(crate::Event::Closed(first_hash, 3, 3)));
+		System::assert_has_event(Event::Dao(crate::Event::Disapproved(first_hash)));
+
+		let (second, second_hash) = propose(CHARLIE, 4, 43);
+		assert_ok!(Dao::vote(Origin::signed(ALICE), ROOM, second_hash, second, Ballot::Aye, None));
+		System::assert_has_event(Event::Dao(crate::Event::Approved(second_hash)));
+		System::assert_last_event(Event::Dao(crate::Event::Closed(second_hash, 4, 0)));
+	});
+}
+
+#[test]
+fn quadratic_tally_closes_motion() {
+	ExtBuilder::build_and_execute(|| {
+		assert_ok!(Dao::set_voting_mode(Origin::signed(ALICE), ROOM, VotingMode::Quadratic));
+		let (index, hash) = propose(ALICE, 5, 42);
+
+		assert_ok!(Dao::vote_quadratic(Origin::signed(ALICE), ROOM, hash, index, true, 3));
+		assert_ok!(Dao::vote_quadratic(Origin::signed(BOB), ROOM, hash, index, true, 2));
+
+		assert!(Dao::proposals(ROOM).is_empty());
+		assert_eq!(Dao::quadratic_tally(ROOM, index), (0, 0));
+		System::assert_has_event(Event::Dao(crate::Event::Approved(hash)));
+		System::assert_last_event(Event::Dao(crate::Event::Closed(hash, 5, 0)));
+	});
+}
+
+#[test]
+fn escalated_motion_is_forwarded_instead_of_executed() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(BOB, 2, 42);
+
+		assert_noop!(
+			Dao::escalate(Origin::signed(BOB), ROOM, hash, index, 2),
+			Error::<Runtime>::NotRootOrPrime
+		);
+		assert_ok!(Dao::escalate(Origin::signed(ALICE), ROOM, hash, index, 2));
+		assert_noop!(
+			Dao::escalate(Origin::signed(ALICE), ROOM, hash, index, 2),
+			Error::<Runtime>::AlreadyEscalated
+		);
+
+		// The escalation collective refuses the motion, so it stays open here, but the vote
+		// that approved it is kept.
+		assert_ok!(Dao::vote(Origin::signed(CHARLIE), ROOM, hash, index, Ballot::Aye, None));
+		System::assert_last_event(Event::Dao(crate::Event::EscalationFailed(
+			ROOM,
+			index,
+			hash,
+			DispatchError::Other("escalation is not supported"),
+		)));
+		assert_eq!(Dao::proposals(ROOM), vec![index]);
+		assert!(Dao::voting(ROOM, index).unwrap().ayes.contains(&CHARLIE));
+		assert!(Dao::escalations(ROOM, index).is_some());
+		assert!(!System::events().iter().any(|r| matches!(
+			r.event,
+			Event::Dao(crate::Event::Approved(..)) | Event::Dao(crate::Event::Executed(..))
+		)));
+	});
+}
+
+#[test]
+fn escalated_motion_has_no_proposer() {
+	ExtBuilder::build_and_execute(|| {
+		let motion = RoomMotion::Single(make_proposal(42));
+		let hash = Dao::motion_hash(&motion);
+
+		let index =
+			<Dao as EscalationHandler<_, _, DispatchError>>::escalate(ROOM, 7, motion, 2).unwrap();
+		System::assert_last_event(Event::Dao(crate::Event::ProposedByEscalation(
+			ROOM, index, hash, 7,
+		)));
+		assert_eq!(Dao::proposer_of(ROOM, index), None);
+		assert_eq!(Dao::escalated_from(ROOM, index), Some(7));
+		assert!(!System::events()
+			.iter()
+			.any(|r| matches!(r.event, Event::Dao(crate::Event::Proposed(..)))));
+
+		assert_ok!(Dao::vote(Origin::signed(ALICE), ROOM, hash, index, Ballot::Aye, None));
+		assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Aye, None));
+		assert_eq!(Dao::escalated_from(ROOM, index), None);
+	});
+}
+
+#[test]
+fn proposer_amends_metadata() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 3, 42);
+		assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Aye, None));
+
+		let metadata = MotionMetadata::<Runtime> {
+			title: b"new title".to_vec().try_into().unwrap(),
+			description_hash: Some(H256::repeat_byte(1)),
+			link: b"ipfs://cid".to_vec().try_into().unwrap(),
+		};
+		assert_noop!(
+			Dao::amend(Origin::signed(BOB), ROOM, index, metadata.clone(), false),
+			Error::<Runtime>::NotProposer
+		);
+
+		assert_ok!(Dao::amend(Origin::signed(ALICE), ROOM, index, metadata.clone(), false));
+		assert_eq!(Dao::metadata_of(ROOM, index), Some(metadata.clone()));
+		assert_eq!(Dao::voting(ROOM, index).unwrap().ayes, vec![ALICE, BOB]);
+
+		assert_ok!(Dao::amend(Origin::signed(ALICE), ROOM, index, metadata.clone(), true));
+		assert_eq!(Dao::voting(ROOM, index).unwrap().ayes, vec![ALICE]);
+		System::assert_last_event(Event::Dao(crate::Event::Amended(
+			ALICE, ROOM, index, metadata, true,
+		)));
+	});
+}
+
+#[test]
+fn abstaining_counts_as_voted() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose(ALICE, 2, 42);
+		let rationale: BoundedVec<u8, MaxRationaleLen> =
+			b"conflict of interest".to_vec().try_into().unwrap();
+
+		assert_ok!(Dao::vote(
+			Origin::signed(BOB),
+			ROOM,
+			hash,
+			index,
+			Ballot::Abstain,
+			Some(rationale.clone())
+		));
+		System::assert_last_event(Event::Dao(crate::Event::Voted(
+			BOB,
+			hash,
+			Ballot::Abstain,
+			3,
+			1,
+			0,
+			1,
+			Some(BlakeTwo256::hash(&rationale[..])),
+		)));
+		assert_noop!(
+			Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Abstain, None),
+			Error::<Runtime>::DuplicateVote
+		);
+
+		// With BOB abstaining, a single nay leaves the ayes short of the threshold.
+		assert_ok!(Dao::vote(Origin::signed(CHARLIE), ROOM, hash, index, Ballot::Nay, None));
+		assert_eq!(Dao::voting(ROOM, index), None);
+		System::assert_has_event(Event::Dao(crate::Event::Disapproved(hash)));
+	});
+}
+
+fn propose_cross_room(who: AccountId, rooms: Vec<RoomIndex>) -> ProposalIndex {
+	let proposal = make_proposal(42);
+	let len = proposal.using_encoded(|p| p.len()) as u32;
+	let index = Dao::cross_room_count();
+	assert_ok!(Dao::propose_cross_room(Origin::signed(who), rooms, Box::new(proposal), len));
+	index
+}
+
+#[test]
+fn cross_room_motion_executes_once_every_room_approves() {
+	ExtBuilder::build_and_execute(|| {
+		let index = propose_cross_room(ALICE, vec![ROOM, OTHER_ROOM]);
+		// ALICE only sits on the council of `ROOM`. Every room needs a majority of its council.
+		let voting = Dao::cross_room_voting(index, ROOM).unwrap();
+		assert_eq!((voting.ayes, voting.threshold), (vec![ALICE], 2));
+		let voting = Dao::cross_room_voting(index, OTHER_ROOM).unwrap();
+		assert!(voting.ayes.is_empty());
+		assert_eq!(voting.threshold, 2);
+
+		assert_noop!(
+			Dao::vote_cross_room(Origin::signed(DAVE), index, ROOM, Ballot::Aye),
+			Error::<Runtime>::NotMember
+		);
+		assert_ok!(Dao::vote_cross_room(Origin::signed(DAVE), index, OTHER_ROOM, Ballot::Aye));
+		assert_ok!(Dao::vote_cross_room(Origin::signed(CHARLIE), index, OTHER_ROOM, Ballot::Aye));
+		System::assert_last_event(Event::Dao(crate::Event::CrossRoomApproved(index, OTHER_ROOM)));
+		assert_noop!(
+			Dao::vote_cross_room(Origin::signed(DAVE), index, OTHER_ROOM, Ballot::Nay),
+			Error::<Runtime>::RoomAlreadyApproved
+		);
+		assert_eq!(Dao::cross_room_motions(index).unwrap().approved, vec![(OTHER_ROOM, 2, 2)]);
+
+		assert_ok!(Dao::vote_cross_room(Origin::signed(BOB), index, ROOM, Ballot::Aye));
+		System::assert_has_event(Event::Dao(crate::Event::CrossRoomApproved(index, ROOM)));
+		// The remark needs a signed origin, so the call fails under the rooms origin.
+		System::assert_last_event(Event::Dao(crate::Event::CrossRoomExecuted(
+			index,
+			Err(DispatchError::BadOrigin),
+		)));
+		assert_eq!(Dao::cross_room_motions(index), None);
+		assert_eq!(Dao::cross_room_voting(index, ROOM), None);
+	});
+}
+
+#[test]
+fn cross_room_motion_is_rejected_by_any_room() {
+	ExtBuilder::build_and_execute(|| {
+		assert_noop!(
+			Dao::propose_cross_room(
+				Origin::signed(ALICE),
+				vec![ROOM],
+				Box::new(make_proposal(42)),
+				100
+			),
+			Error::<Runtime>::WrongRoomCount
+		);
+		assert_noop!(
+			Dao::propose_cross_room(
+				Origin::signed(ALICE),
+				vec![ROOM, ROOM],
+				Box::new(make_proposal(42)),
+				100
+			),
+			Error::<Runtime>::DuplicateRoom
+		);
+
+		let index = propose_cross_room(ALICE, vec![ROOM, OTHER_ROOM]);
+		assert_ok!(Dao::vote_cross_room(Origin::signed(DAVE), index, OTHER_ROOM, Ballot::Nay));
+		System::assert_last_event(Event::Dao(crate::Event::CrossRoomRejected(
+			index,
+			Some(OTHER_ROOM),
+		)));
+		assert_eq!(Dao::cross_room_motions(index), None);
+		assert_eq!(Dao::cross_room_voting(index, ROOM), None);
+		assert_eq!(Dao::cross_rooms_of(ROOM, index), None);
+	});
+}
+
+#[test]
+fn dissolved_room_drops_out_of_cross_room_motions() {
+	ExtBuilder::build_and_execute(|| {
+		let index = propose_cross_room(ALICE, vec![ROOM, OTHER_ROOM]);
+		assert_eq!(Dao::cross_rooms_of(OTHER_ROOM, index), Some(()));
+
+		assert_ok!(
+			<Dao as CollectiveHandler<RoomIndex, u64, DispatchError>>::remove_room_collective_info(
+				OTHER_ROOM
+			)
+		);
+		while Dao::dissolving_rooms(OTHER_ROOM).is_some() {
+			Dao::on_initialize(1);
+		}
+
+		// A single room is left, so the motion is no longer a cross-room motion.
+		System::assert_has_event(Event::Dao(crate::Event::CrossRoomRejected(
+			index,
+			Some(OTHER_ROOM),
+		)));
+		assert_eq!(Dao::cross_room_motions(index), None);
+		assert_eq!(Dao::cross_room_voting(index, ROOM), None);
+		assert_eq!(Dao::cross_rooms_of(ROOM, index), None);
+		assert_eq!(Dao::cross_rooms_of(OTHER_ROOM, index), None);
+	});
+}
+
+#[test]
+fn rooms_origin_needs_a_majority_of_every_council() {
+	let origin = |rooms: Vec<(RoomIndex, MemberCount, MemberCount)>| {
+		Origin::from(RoomRawOrigin::<AccountId, ()>::Rooms(rooms))
+	};
+	assert_eq!(
+		EnsureRooms::<AccountId, ()>::try_origin(origin(vec![(ROOM, 2, 3)])).ok(),
+		Some(vec![ROOM])
+	);
+	assert!(EnsureRooms::<AccountId, ()>::try_origin(origin(vec![
+		(ROOM, 2, 3),
+		(OTHER_ROOM, 1, 2)
+	]))
+	.is_err());
+	assert!(EnsureRooms::<AccountId, ()>::try_origin(origin(vec![])).is_err());
+}
+
+#[test]
+fn room_proportion_origin_yields_the_room() {
+	let origin = |n, m| Origin::from(RoomRawOrigin::<AccountId, ()>::RoomMembers(ROOM, n, m));
+	assert_eq!(
+		EnsureRoomProportionMoreThan::<AccountId, (), 1, 2>::try_origin(origin(2, 3)).ok(),
+		Some(ROOM)
+	);
+	assert!(EnsureRoomProportionMoreThan::<AccountId, (), 1, 2>::try_origin(origin(1, 2)).is_err());
+	assert!(EnsureRoomProportionMoreThan::<AccountId, (), 1, 2>::try_origin(Origin::from(
+		RoomRawOrigin::<AccountId, ()>::Members(2, 3)
+	))
+	.is_err());
+}
+
+#[test]
+fn expired_cross_room_motion_can_be_closed() {
+	ExtBuilder::build_and_execute(|| {
+		let index = propose_cross_room(CHARLIE, vec![ROOM, OTHER_ROOM]);
+		assert_noop!(
+			Dao::close_cross_room(Origin::signed(DAVE), index),
+			Error::<Runtime>::VoteNotExpired
+		);
+
+		System::set_block_number(1 + MotionDuration::get());
+		assert_noop!(
+			Dao::vote_cross_room(Origin::signed(DAVE), index, OTHER_ROOM, Ballot::Aye),
+			Error::<Runtime>::VoteExpire
+		);
+		assert_ok!(Dao::close_cross_room(Origin::signed(DAVE), index));
+		System::assert_last_event(Event::Dao(crate::Event::CrossRoomRejected(index, None)));
+		assert_eq!(Dao::cross_room_motions(index), None);
+	});
+}
+
+fn register_recurring(period: u64, repetitions: u32) -> u32 {
+	let proposal = make_proposal(42);
+	let len = proposal.using_encoded(|p| p.len()) as u32;
+	let id = Dao::recurring_count(ROOM);
+	assert_ok!(Dao::register_recurring(
+		Origin::signed(ALICE),
+		ROOM,
+		2,
+		Box::new(proposal),
+		period,
+		repetitions,
+		len
+	));
+	id
+}
+
+#[test]
+fn recurring_motion_is_proposed_every_period() {
+	ExtBuilder::build_and_execute(|| {
+		let proposal = make_proposal(42);
+		let hash = BlakeTwo256::hash_of(&proposal);
+		assert_noop!(
+			Dao::register_recurring(
+				Origin::signed(BOB),
+				ROOM,
+				2,
+				Box::new(proposal.clone()),
+				4,
+				2,
+				100
+			),
+			Error::<Runtime>::NotRoomOwner
+		);
+		assert_noop!(
+			Dao::register_recurring(
+				Origin::signed(ALICE),
+				ROOM,
+				2,
+				Box::new(proposal.clone()),
+				0,
+				2,
+				100
+			),
+			Error::<Runtime>::WrongRecurrence
+		);
+		// The room council has three seats, so a single vote is not a majority.
+		assert_noop!(
+			Dao::register_recurring(Origin::signed(ALICE), ROOM, 1, Box::new(proposal), 4, 2, 100),
+			Error::<Runtime>::RecurringThresholdTooLow
+		);
+
+		let id = register_recurring(4, 2);
+		assert_eq!(Dao::recurring_queue(5), vec![(ROOM, id)]);
+
+		System::set_block_number(5);
+		Dao::on_initialize(5);
+		System::assert_last_event(Event::Dao(crate::Event::RecurringProposed(ROOM, id, Ok(0))));
+		assert_eq!(Dao::proposal_of(ROOM, 0), Some(RoomMotion::Single(make_proposal(42))));
+		// The council votes every instance, the root included.
+		assert!(Dao::voting(ROOM, 0).unwrap().ayes.is_empty());
+		assert_eq!(Dao::recurring_motions(ROOM, id).unwrap().remaining, 1);
+		assert_eq!(Dao::recurring_queue(9), vec![(ROOM, id)]);
+
+		assert_ok!(Dao::vote(Origin::signed(ALICE), ROOM, hash, 0, Ballot::Aye, None));
+		assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, 0, Ballot::Aye, None));
+		assert_eq!(Dao::proposals(ROOM), Vec::<ProposalIndex>::new());
+
+		System::set_block_number(9);
+		Dao::on_initialize(9);
+		System::assert_last_event(Event::Dao(crate::Event::RecurringProposed(ROOM, id, Ok(1))));
+		assert_eq!(Dao::recurring_motions(ROOM, id), None);
+		assert!(Dao::recurring_queue(13).is_empty());
+	});
+}
+
+#[test]
+fn recurring_motion_is_cancelled_by_a_motion() {
+	ExtBuilder::build_and_execute(|| {
+		let id = register_recurring(4, 3);
+
+		let proposal = Call::Dao(crate::Call::cancel_recurring { room_id: ROOM, id });
+		let hash = BlakeTwo256::hash_of(&proposal);
+		let len = proposal.using_encoded(|p| p.len()) as u32;
+		assert_ok!(Dao::propose(
+			Origin::signed(BOB),
+			ROOM,
+			2,
+			Box::new(proposal),
+			Default::default(),
+			len
+		));
+		assert_ok!(Dao::vote(Origin::signed(CHARLIE), ROOM, hash, 0, Ballot::Aye, None));
+		System::assert_has_event(Event::Dao(crate::Event::RecurringCancelled(ROOM, id)));
+		System::assert_has_event(Event::Dao(crate::Event::Executed(hash, Ok(()))));
+		assert_eq!(Dao::recurring_motions(ROOM, id), None);
+		assert!(Dao::recurring_queue(5).is_empty());
+
+		System::set_block_number(5);
+		Dao::on_initialize(5);
+		assert_eq!(Dao::proposal_count(ROOM), 1);
+	});
+}
+
+#[test]
+fn dissolution_spans_blocks() {
+	ExtBuilder::build_and_execute(|| {
+		let (_, first) = propose(ALICE, 3, 1);
+		let (_, second) = propose(BOB, 3, 2);
+		let (third_index, third) = propose(CHARLIE, 3, 3);
+		assert_ok!(Dao::veto(Origin::signed(CHARLIE), ROOM, third, third_index));
+		register_recurring(4, 2);
+
+		let weight =
+			<Dao as CollectiveHandler<RoomIndex, u64, DispatchError>>::remove_room_collective_info(
+				ROOM,
+			)
+			.unwrap();
+		assert!(weight > 0);
+		System::assert_has_event(Event::Dao(crate::Event::Disapproved(first)));
+		System::assert_has_event(Event::Dao(crate::Event::Disapproved(second)));
+		assert!(Dao::proposals(ROOM).is_empty());
+		assert!(Dao::dissolving_rooms(ROOM).is_some());
+
+		let mut block = 1;
+		while Dao::dissolving_rooms(ROOM).is_some() {
+			block += 1;
+			assert!(block < 20, "dissolution did not finish");
+			System::set_block_number(block);
+			Dao::on_initialize(block);
+		}
+		System::assert_last_event(Event::Dao(crate::Event::RoomDissolved(ROOM)));
+		assert_eq!(Voting::<Runtime>::iter_prefix(ROOM).count(), 0);
+		assert_eq!(ProposalOf::<Runtime>::iter_prefix(ROOM).count(), 0);
+		assert_eq!(Dao::blacklist(ROOM, third), None);
+		assert_eq!(Dao::recurring_motions(ROOM, 0), None);
+		assert_eq!(Dao::proposal_count(ROOM), 0);
+	});
+}
+
+#[test]
+fn dissolution_cancels_spends_in_their_grace_period() {
+	ExtBuilder::build_and_execute(|| {
+		let (_, open) = propose(ALICE, 3, 1);
+		let (index, hash) = propose_spend(BOB, 1);
+		assert_ok!(Dao::vote(Origin::signed(CHARLIE), ROOM, hash, index, Ballot::Aye, None));
+		assert!(Dao::grace_periods(ROOM, index).is_some());
+
+		assert_ok!(
+			<Dao as CollectiveHandler<RoomIndex, u64, DispatchError>>::remove_room_collective_info(
+				ROOM
+			)
+		);
+		System::assert_has_event(Event::Dao(crate::Event::Disapproved(open)));
+		System::assert_has_event(Event::Dao(crate::Event::Disapproved(hash)));
+		assert_eq!(Dao::grace_periods(ROOM, index), None);
+		assert_eq!(
+			closed_outcomes(),
+			vec![(Some(BOB), MotionOutcome::Approved), (Some(ALICE), MotionOutcome::Overruled)]
+		);
+
+		// Nothing runs when the grace period would have ended.
+		System::set_block_number(1 + GracePeriod::get());
+		Dao::on_initialize(1 + GracePeriod::get());
+		assert!(!System::events()
+			.iter()
+			.any(|r| matches!(r.event, Event::Dao(crate::Event::Executed(..)))));
+	});
+}
+
+#[test]
+fn dissolving_room_cancels_grace_periods_over_several_blocks() {
+	ExtBuilder::build_and_execute(|| {
+		let hashes: Vec<_> = (1..=3)
+			.map(|value| {
+				let (index, hash) = propose_spend(ALICE, value);
+				assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Aye, None));
+				hash
+			})
+			.collect();
+
+		assert_ok!(
+			<Dao as CollectiveHandler<RoomIndex, u64, DispatchError>>::remove_room_collective_info(
+				ROOM
+			)
+		);
+		// `DissolutionLimit` is two, so one spend waits for the next block.
+		assert_eq!(GracePeriods::<Runtime>::iter_prefix(ROOM).count(), 1);
+		let proposal = make_proposal(42);
+		assert_noop!(
+			Dao::propose(
+				Origin::signed(ALICE),
+				ROOM,
+				2,
+				Box::new(proposal),
+				Default::default(),
+				100
+			),
+			Error::<Runtime>::RoomDissolving
+		);
+
+		System::set_block_number(2);
+		Dao::on_initialize(2);
+		assert_eq!(GracePeriods::<Runtime>::iter_prefix(ROOM).count(), 0);
+		for hash in hashes {
+			System::assert_has_event(Event::Dao(crate::Event::Disapproved(hash)));
+		}
+	});
+}
+
+fn propose_spend(who: AccountId, value: u64) -> (ProposalIndex, H256) {
+	let proposal = make_spend_proposal(value);
+	let hash = BlakeTwo256::hash_of(&proposal);
+	let index = Dao::proposal_count(ROOM);
+	assert_ok!(Dao::propose(
+		Origin::signed(who),
+		ROOM,
+		2,
+		Box::new(proposal),
+		Default::default(),
+		100
+	));
+	(index, hash)
+}
+
+#[test]
+fn quadratic_tally_outlives_the_grace_period() {
+	ExtBuilder::build_and_execute(|| {
+		assert_ok!(Dao::set_voting_mode(Origin::signed(ALICE), ROOM, VotingMode::Quadratic));
+		let (index, hash) = propose_spend(ALICE, 1);
+		assert_ok!(Dao::vote_quadratic(Origin::signed(BOB), ROOM, hash, index, true, 2));
+		assert!(Dao::grace_periods(ROOM, index).is_some());
+		// the motion is executed with the tally that approved it
+		assert_eq!(Dao::quadratic_tally(ROOM, index), (2, 0));
+
+		System::set_block_number(1 + GracePeriod::get());
+		Dao::on_initialize(1 + GracePeriod::get());
+		assert_eq!(Dao::grace_periods(ROOM, index), None);
+		assert_eq!(Dao::quadratic_tally(ROOM, index), (0, 0));
+		// The remark needs a signed origin, so the call fails under the rooms origin.
+		System::assert_has_event(Event::Dao(crate::Event::Executed(
+			hash,
+			Err((0, DispatchError::BadOrigin)),
+		)));
+	});
+}
+
+#[test]
+fn dissenter_rage_quits_without_committed_funds() {
+	ExtBuilder::build_and_execute(|| {
+		let (index, hash) = propose_spend(ALICE, 1);
+		assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Nay, None));
+		assert_noop!(Dao::ragequit(Origin::signed(BOB), ROOM), Error::<Runtime>::NotDissenter);
+		assert_ok!(Dao::vote(Origin::signed(CHARLIE), ROOM, hash, index, Ballot::Aye, None));
+		System::assert_has_event(Event::Dao(crate::Event::GracePeriodStarted(hash, 4)));
+
+		assert_noop!(Dao::ragequit(Origin::signed(ALICE), ROOM), Error::<Runtime>::NotDissenter);
+		assert_ok!(Dao::ragequit(Origin::signed(BOB), ROOM));
+		let payout = (ROOM_FUNDS - PENDING_SPENDS) / 3;
+		System::assert_last_event(Event::Dao(crate::Event::RageQuit(BOB, ROOM, payout)));
+		assert_eq!(ListenHandlerMock::get_room_council(ROOM).unwrap(), vec![ALICE, CHARLIE]);
+		assert!(Dao::grace_periods(ROOM, index).unwrap().0.nays.is_empty());
+	});
+}
+
+#[test]
+fn grace_period_executions_are_capped_per_block() {
+	ExtBuilder::build_and_execute(|| {
+		for value in 1..=3 {
+			let (index, hash) = propose_spend(ALICE, value);
+			assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Aye, None));
+		}
+		assert_eq!(Dao::grace_queue(4), vec![(ROOM, 0), (ROOM, 1), (ROOM, 2)]);
+
+		System::set_block_number(4);
+		Dao::on_initialize(4);
+		assert_eq!(Dao::grace_periods(ROOM, 0), None);
+		assert_eq!(Dao::grace_periods(ROOM, 1), None);
+		assert!(Dao::grace_periods(ROOM, 2).is_some());
+		assert_eq!(Dao::grace_queue(5), vec![(ROOM, 2)]);
+
+		System::set_block_number(5);
+		Dao::on_initialize(5);
+		assert_eq!(Dao::grace_periods(ROOM, 2), None);
+		assert!(Dao::proposal_of(ROOM, 2).is_none());
+	});
+}
+
+fn propose_batch(calls: Vec<Call>, mode: BatchMode) -> H256 {
+	let motion = RoomMotion::Batch(calls.clone(), mode);
+	let index = Dao::proposal_count(ROOM);
+	assert_ok!(Dao::propose_batch(
+		Origin::signed(ALICE),
+		ROOM,
+		2,
+		calls,
+		mode,
+		Default::default(),
+		1000
+	));
+	let hash = Dao::motion_hash(&motion);
+	assert_ok!(Dao::vote(Origin::signed(BOB), ROOM, hash, index, Ballot::Aye, None));
+	hash
+}
+
+#[test]
+fn executed_reports_first_failing_call_of_a_batch() {
+	ExtBuilder::build_and_execute(|| {
+		let id = register_recurring(4, 3);
+		let cancel = Call::Dao(crate::Call::cancel_recurring { room_id: ROOM, id });
+
+		let hash = propose_batch(vec![cancel.clone(), make_proposal(1)], BatchMode::AllOrNothing);
+		System::assert_has_event(Event::Dao(crate::Event::Executed(
+			hash,
+			Err((1, DispatchError::BadOrigin)),
+		)));
+		assert!(Dao::recurring_motions(ROOM, id).is_some());
+
+		let hash = propose_batch(vec![cancel, make_proposal(1)], BatchMode::BestEffort);
+		System::assert_has_event(Event::Dao(crate::Event::Executed(
+			hash,
+			Err((1, DispatchError::BadOrigin)),
+		)));
+		assert_eq!(Dao::recurring_motions(ROOM, id), None);
+	});
+}
+
+#[test]
+fn migration_to_v4_keys_motions_by_index() {
+	ExtBuilder::build().execute_with(|| {
+		use frame_support::{
+			storage::{storage_prefix, unhashed},
+			traits::{GetStorageVersion, StorageVersion},
+			Blake2_128Concat, StorageHasher,
+		};
+
+		let hash_key = |item: &[u8], hash: H256| {
+			[
+				&storage_prefix(b"Dao", item)[..],
+				&Blake2_128Concat::hash(&ROOM.encode()),
+				&Blake2_128Concat::hash(&hash.encode()),
+			]
+			.concat()
+		};
+		let proposal = make_proposal(42);
+		let hash = BlakeTwo256::hash_of(&proposal);
+		let unvoted = make_proposal(43);
+		let unvoted_hash = BlakeTwo256::hash_of(&unvoted);
+		let reason = b"a reason longer than a title".to_vec();
+
+		// bare calls and votes keyed by the proposal hash, with a reason and no abstentions
+		unhashed::put(&hash_key(b"ProposalOf", hash), &proposal);
+		unhashed::put(&hash_key(b"ProposalOf", unvoted_hash), &unvoted);
+		// index, reason, threshold, ayes, nays, end
+		let old_votes =
+			(5u32, Some(reason.clone()), 3u32, vec![ALICE], Vec::<AccountId>::new(), 6u64);
+		unhashed::put(&hash_key(b"Voting", hash), &old_votes);
+		unhashed::put(&Proposals::<Runtime>::hashed_key_for(ROOM), &vec![hash, unvoted_hash]);
+		ProposalCount::<Runtime>::insert(ROOM, 6);
+		StorageVersion::new(0).put::<Dao>();
+
+		migrations::migrate::<Runtime, ()>();
+		assert_eq!(Dao::on_chain_storage_version(), 4);
+		assert_eq!(Dao::proposals(ROOM), vec![5]);
+		assert_eq!(Dao::proposal_of(ROOM, 5), Some(RoomMotion::Single(proposal)));
+		assert_eq!(Dao::proposals_by_hash(ROOM, hash), vec![5]);
+		assert_eq!(Dao::proposal_count(ROOM), 6);
+		let voting = Dao::voting(ROOM, 5).unwrap();
+		assert_eq!((voting.index, voting.threshold, voting.end), (5, 3, 6));
+		assert_eq!(voting.ayes, vec![ALICE]);
+		assert!(voting.nays.is_empty());
+		assert!(voting.abstentions.is_empty());
+		assert_eq!(
+			Dao::metadata_of(ROOM, 5).unwrap().title.into_inner(),
+			reason[..MaxTitleLen::get() as usize].to_vec()
+		);
+
+		// the call without votes is dropped and no hash-keyed entry is left
+		assert!(Dao::proposals_by_hash(ROOM, unvoted_hash).is_empty());
+		assert!(!unhashed::exists(&hash_key(b"ProposalOf", hash)));
+		assert!(!unhashed::exists(&hash_key(b"ProposalOf", unvoted_hash)));
+		assert!(!unhashed::exists(&hash_key(b"Voting", hash)));
+		assert_eq!(ProposalOf::<Runtime>::iter_prefix(ROOM).count(), 1);
+	});
+}
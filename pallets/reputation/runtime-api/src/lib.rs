@@ -0,0 +1,29 @@
+// Copyright 2021 LISTEN Developer.
+// This file is part of LISTEN.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the reputation pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait ReputationApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The reputation of a member in a room, with decay applied up to the current block.
+		fn reputation(room_id: u64, who: AccountId) -> u64;
+	}
+}
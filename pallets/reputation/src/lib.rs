@@ -0,0 +1,207 @@
+// Copyright 2021 LISTEN Developer.
+// This file is part of LISTEN.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Reputation
+//!
+//! Per-room reputation scores earned through governance participation.
+//!
+//! The pallet observes the motions of pallet-dao as they close. Every member who voted or
+//! abstained earns `ParticipationPoints`, members whose vote matched the outcome earn
+//! `MajorityPoints` on top, and the proposer earns `ApprovedProposalPoints` or loses
+//! `RejectedProposalPenalty`. Only the decision of the council counts: voters of a motion closed
+//! by a veto or by root just earn `ParticipationPoints`, and its proposer keeps their score.
+//! Scores lose `Decay` of their value every `DecayPeriod` blocks.
+//!
+//! Scores are available through the `ReputationApi` runtime API and as a `VoteWeightSource`.
+//! As a vote weight, every member counts `BaseVoteWeight` on top of their score, so that a room
+//! without any scores yet still votes one member one vote. The scores of a room are dropped when
+//! it is dissolved.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod mock;
+mod tests;
+
+pub use crate::pallet::*;
+use codec::{Decode, Encode};
+use frame_support::{traits::Get, weights::Weight};
+use listen_primitives::traits::{
+	GovernanceObserver, MotionOutcome, RoomTreasuryHandler, VoteWeightSource,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{CheckedDiv, Saturating, Zero},
+	PerThing, Perbill, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+pub type RoomIndex = u64;
+
+/// The reputation of a member in a room.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct ReputationScore<BlockNumber> {
+	/// The score as of the start of `period`.
+	pub score: u64,
+	/// The decay period the score was last brought up to date in.
+	pub period: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	#[pallet::disable_frame_system_supertrait_check]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>>
+			+ Into<<Self as frame_system::Config>::Event>
+			+ IsType<<Self as frame_system::Config>::Event>;
+		/// Points for voting or abstaining on a motion.
+		#[pallet::constant]
+		type ParticipationPoints: Get<u64>;
+		/// Extra points for voting aye on an approved or nay on a rejected motion.
+		#[pallet::constant]
+		type MajorityPoints: Get<u64>;
+		/// Points for proposing a motion that is approved.
+		#[pallet::constant]
+		type ApprovedProposalPoints: Get<u64>;
+		/// Points lost for proposing a motion that is rejected.
+		#[pallet::constant]
+		type RejectedProposalPenalty: Get<u64>;
+		/// Number of blocks after which scores decay.
+		#[pallet::constant]
+		type DecayPeriod: Get<Self::BlockNumber>;
+		/// Fraction of a score lost every `DecayPeriod`.
+		#[pallet::constant]
+		type Decay: Get<Perbill>;
+		/// Vote weight of every member on top of their score.
+		#[pallet::constant]
+		type BaseVoteWeight: Get<u64>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	#[pallet::generate_store(pub (super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Scores changed after a motion closed. \[room_id, new_scores\]
+		ReputationUpdated(RoomIndex, Vec<(T::AccountId, u64)>),
+		/// The room was dissolved and its scores were dropped. \[room_id\]
+		ReputationCleared(RoomIndex),
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn scores)]
+	pub type Scores<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		ReputationScore<T::BlockNumber>,
+		ValueQuery,
+	>;
+
+	impl<T: Config> Pallet<T> {
+		/// The reputation of a member in a room, with decay applied up to the current block.
+		pub fn reputation(room_id: RoomIndex, who: &T::AccountId) -> u64 {
+			Self::decayed(Self::scores(room_id, who), Self::current_period())
+		}
+
+		fn current_period() -> T::BlockNumber {
+			frame_system::Pallet::<T>::block_number()
+				.checked_div(&T::DecayPeriod::get())
+				.unwrap_or_else(Zero::zero)
+		}
+
+		fn decayed(record: ReputationScore<T::BlockNumber>, period: T::BlockNumber) -> u64 {
+			let elapsed: usize = period.saturating_sub(record.period).saturated_into();
+			let kept = Perbill::one().saturating_sub(T::Decay::get()).saturating_pow(elapsed);
+			kept.mul_floor(record.score)
+		}
+
+		// Brings the score up to date, applies `f` and returns the new score.
+		fn update_score(room_id: RoomIndex, who: &T::AccountId, f: impl FnOnce(u64) -> u64) -> u64 {
+			let period = Self::current_period();
+			<Scores<T>>::mutate(room_id, who, |record| {
+				record.score = f(Self::decayed(record.clone(), period));
+				record.period = period;
+				record.score
+			})
+		}
+	}
+}
+
+impl<T: Config> GovernanceObserver<RoomIndex, T::AccountId> for Pallet<T> {
+	fn on_motion_closed(
+		room_id: RoomIndex,
+		proposer: Option<T::AccountId>,
+		outcome: MotionOutcome,
+		ayes: &[T::AccountId],
+		nays: &[T::AccountId],
+		abstentions: &[T::AccountId],
+	) {
+		let majority = match outcome {
+			MotionOutcome::Approved => ayes,
+			MotionOutcome::Rejected => nays,
+			MotionOutcome::Overruled => &[],
+		};
+		let mut new_scores = Vec::with_capacity(ayes.len() + nays.len() + abstentions.len() + 1);
+
+		for who in ayes.iter().chain(nays.iter()).chain(abstentions.iter()) {
+			let mut points = T::ParticipationPoints::get();
+			if majority.contains(who) {
+				points = points.saturating_add(T::MajorityPoints::get());
+			}
+			let score = Self::update_score(room_id, who, |score| score.saturating_add(points));
+			new_scores.push((who.clone(), score));
+		}
+
+		if let Some(proposer) = proposer {
+			let score = match outcome {
+				MotionOutcome::Approved => Self::update_score(room_id, &proposer, |score| {
+					score.saturating_add(T::ApprovedProposalPoints::get())
+				}),
+				MotionOutcome::Rejected => Self::update_score(room_id, &proposer, |score| {
+					score.saturating_sub(T::RejectedProposalPenalty::get())
+				}),
+				MotionOutcome::Overruled => Self::reputation(room_id, &proposer),
+			};
+			new_scores.push((proposer, score));
+		}
+
+		Self::deposit_event(Event::ReputationUpdated(room_id, new_scores));
+	}
+}
+
+impl<T: Config> VoteWeightSource<RoomIndex, T::AccountId> for Pallet<T> {
+	fn vote_weight(room_id: RoomIndex, who: &T::AccountId) -> u64 {
+		Self::reputation(room_id, who).saturating_add(T::BaseVoteWeight::get())
+	}
+}
+
+impl<T: Config> RoomTreasuryHandler<RoomIndex> for Pallet<T> {
+	fn remove_room_treasury_info(room_id: RoomIndex) -> Weight {
+		// Only members who took part in a motion have a score, so there are few of them.
+		let removed = <Scores<T>>::clear_prefix(room_id, u32::MAX, None).loops as u64;
+		Self::deposit_event(Event::ReputationCleared(room_id));
+		T::DbWeight::get().reads_writes(removed, removed.saturating_add(1))
+	}
+}
@@ -0,0 +1,108 @@
+//! Unit tests for the reputation module.
+
+#![cfg(test)]
+
+use super::*;
+use mock::{Event, *};
+
+#[test]
+fn approved_motion_rewards_participants() {
+	ExtBuilder::build().execute_with(|| {
+		Reputation::on_motion_closed(
+			ROOM,
+			Some(ALICE),
+			MotionOutcome::Approved,
+			&[ALICE, BOB],
+			&[CHARLIE],
+			&[],
+		);
+
+		assert_eq!(Reputation::reputation(ROOM, &ALICE), 10 + 5 + 20);
+		assert_eq!(Reputation::reputation(ROOM, &BOB), 10 + 5);
+		assert_eq!(Reputation::reputation(ROOM, &CHARLIE), 10);
+		System::assert_last_event(Event::Reputation(crate::Event::ReputationUpdated(
+			ROOM,
+			vec![(ALICE, 15), (BOB, 15), (CHARLIE, 10), (ALICE, 35)],
+		)));
+	});
+}
+
+#[test]
+fn rejected_motion_penalizes_proposer() {
+	ExtBuilder::build().execute_with(|| {
+		Reputation::on_motion_closed(
+			ROOM,
+			Some(ALICE),
+			MotionOutcome::Rejected,
+			&[ALICE],
+			&[BOB],
+			&[CHARLIE],
+		);
+
+		// The penalty can not take a score below zero.
+		assert_eq!(Reputation::reputation(ROOM, &ALICE), 0);
+		assert_eq!(Reputation::reputation(ROOM, &BOB), 10 + 5);
+		assert_eq!(Reputation::reputation(ROOM, &CHARLIE), 10);
+		assert_eq!(Reputation::reputation(ROOM + 1, &BOB), 0);
+	});
+}
+
+#[test]
+fn overruled_motion_only_rewards_participation() {
+	ExtBuilder::build().execute_with(|| {
+		Reputation::on_motion_closed(ROOM, Some(ALICE), MotionOutcome::Approved, &[BOB], &[], &[]);
+		Reputation::on_motion_closed(
+			ROOM,
+			Some(ALICE),
+			MotionOutcome::Overruled,
+			&[BOB],
+			&[CHARLIE],
+			&[],
+		);
+
+		assert_eq!(Reputation::reputation(ROOM, &ALICE), 20);
+		assert_eq!(Reputation::reputation(ROOM, &BOB), 15 + 10);
+		assert_eq!(Reputation::reputation(ROOM, &CHARLIE), 10);
+	});
+}
+
+#[test]
+fn scores_decay_every_period() {
+	ExtBuilder::build().execute_with(|| {
+		Reputation::on_motion_closed(ROOM, None, MotionOutcome::Approved, &[BOB], &[], &[]);
+		assert_eq!(Reputation::vote_weight(ROOM, &BOB), 15 + 1);
+
+		System::set_block_number(DecayPeriod::get());
+		assert_eq!(Reputation::vote_weight(ROOM, &BOB), 7 + 1);
+
+		Reputation::on_motion_closed(ROOM, None, MotionOutcome::Approved, &[BOB], &[], &[]);
+		assert_eq!(Reputation::reputation(ROOM, &BOB), 7 + 15);
+		assert_eq!(Reputation::scores(ROOM, BOB), ReputationScore { score: 22, period: 1 });
+
+		System::set_block_number(DecayPeriod::get() * 3);
+		assert_eq!(Reputation::reputation(ROOM, &BOB), 5);
+	});
+}
+
+#[test]
+fn members_without_score_still_weigh() {
+	ExtBuilder::build().execute_with(|| {
+		assert_eq!(Reputation::reputation(ROOM, &ALICE), 0);
+		assert_eq!(Reputation::vote_weight(ROOM, &ALICE), BaseVoteWeight::get());
+	});
+}
+
+#[test]
+fn dissolution_drops_the_scores_of_the_room() {
+	ExtBuilder::build().execute_with(|| {
+		Reputation::on_motion_closed(ROOM, None, MotionOutcome::Approved, &[ALICE, BOB], &[], &[]);
+		Reputation::on_motion_closed(ROOM + 1, None, MotionOutcome::Approved, &[BOB], &[], &[]);
+
+		Reputation::remove_room_treasury_info(ROOM);
+
+		assert_eq!(Reputation::reputation(ROOM, &ALICE), 0);
+		assert_eq!(Reputation::reputation(ROOM, &BOB), 0);
+		assert_eq!(Reputation::reputation(ROOM + 1, &BOB), 15);
+		System::assert_last_event(Event::Reputation(crate::Event::ReputationCleared(ROOM)));
+	});
+}
@@ -0,0 +1,549 @@
+// Copyright 2021 LISTEN Developer.
+// This file is part of LISTEN.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Room Elections
+//!
+//! Room members elect the council of their room.
+//!
+//! Any room member can stand as a candidate by reserving `CandidacyBond`, and any room member
+//! can approve of candidates by reserving a stake of at least `VotingBond`. Every `TermDuration`
+//! blocks a sequential Phragmén tally is run per room. The `DesiredMembers` best backed winners
+//! become the room council, which is pushed to the room through `ListenHandler`, and the next
+//! `DesiredRunnersUp` winners are kept as runners-up. Members and runners-up stand again in the
+//! next election without having to re-submit their candidacy. A member who steps down is
+//! replaced by the best backed runner-up. At most `MaxElectionsPerBlock` rooms hold their
+//! election in a block; the others hold it in the following blocks.
+//!
+//! When a room is dissolved, all the bonds and stakes held for its elections are returned.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod mock;
+mod tests;
+
+pub use crate::pallet::*;
+use codec::{Decode, Encode};
+use frame_support::{
+	ensure,
+	traits::{Currency, CurrencyToVote, Get, ReservableCurrency},
+	weights::Weight,
+};
+use frame_system::ensure_signed;
+use listen_primitives::traits::{ListenHandler, RoomTreasuryHandler};
+use scale_info::TypeInfo;
+use sp_npos_elections::{seq_phragmen, ElectionResult, ExtendedBalance};
+use sp_runtime::{
+	traits::{One, Zero},
+	Perbill, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+pub type RoomIndex = u64;
+
+/// The ballot of a room member.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Voter<AccountId, Balance> {
+	/// The candidates the voter approves of.
+	pub votes: Vec<AccountId>,
+	/// The reserved amount that backs the votes.
+	pub stake: Balance,
+}
+
+/// A member or runner-up of a room council.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SeatHolder<AccountId, Balance> {
+	/// The holder of the seat.
+	pub who: AccountId,
+	/// The stake that backed the holder in the last election.
+	pub stake: Balance,
+	/// The candidacy bond, reserved as long as the seat is held.
+	pub deposit: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::config]
+	#[pallet::disable_frame_system_supertrait_check]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>>
+			+ Into<<Self as frame_system::Config>::Event>
+			+ IsType<<Self as frame_system::Config>::Event>;
+		/// The currency bonds and stakes are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Converts stakes to the vote weight of the tally and back.
+		type CurrencyToVote: CurrencyToVote<BalanceOf<Self>>;
+		type ListenHandler: ListenHandler<u64, Self::AccountId, DispatchError, u128>;
+		/// Amount reserved to stand as a candidate.
+		#[pallet::constant]
+		type CandidacyBond: Get<BalanceOf<Self>>;
+		/// Minimum stake of a ballot.
+		#[pallet::constant]
+		type VotingBond: Get<BalanceOf<Self>>;
+		/// Number of blocks between two elections of a room.
+		#[pallet::constant]
+		type TermDuration: Get<Self::BlockNumber>;
+		/// Number of council members to elect.
+		#[pallet::constant]
+		type DesiredMembers: Get<u32>;
+		/// Number of runners-up to keep.
+		#[pallet::constant]
+		type DesiredRunnersUp: Get<u32>;
+		/// Maximum number of candidates of a room, not counting members and runners-up.
+		#[pallet::constant]
+		type MaxCandidates: Get<u32>;
+		/// Maximum number of voters of a room.
+		#[pallet::constant]
+		type MaxVoters: Get<u32>;
+		/// Maximum number of candidates a voter can approve of.
+		#[pallet::constant]
+		type MaxVotesPerVoter: Get<u32>;
+		/// Maximum number of rooms to hold an election in per block.
+		#[pallet::constant]
+		type MaxElectionsPerBlock: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	#[pallet::generate_store(pub (super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A room member submitted a candidacy. \[room_id, candidate\]
+		CandidateSubmitted(RoomIndex, T::AccountId),
+		/// A candidate, member or runner-up withdrew and got the bond back. \[room_id, who\]
+		Renounced(RoomIndex, T::AccountId),
+		/// A room member voted. \[room_id, voter, votes, stake\]
+		Voted(RoomIndex, T::AccountId, Vec<T::AccountId>, BalanceOf<T>),
+		/// A voter withdrew the ballot and got the stake back. \[room_id, voter\]
+		VoterRemoved(RoomIndex, T::AccountId),
+		/// A new council was elected and pushed to the room. \[room_id, members\]
+		NewTerm(RoomIndex, Vec<(T::AccountId, BalanceOf<T>)>),
+		/// No candidate got any backing; the room council was left as it was. \[room_id\]
+		EmptyTerm(RoomIndex),
+		/// The election failed; the room council was left as it was. \[room_id\]
+		ElectionError(RoomIndex),
+		/// The room was dissolved; its bonds and stakes were returned. \[room_id\]
+		RoomRemoved(RoomIndex),
+	}
+
+	/// Current council members of a room, best backed first.
+	#[pallet::storage]
+	#[pallet::getter(fn members)]
+	pub type Members<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Vec<SeatHolder<T::AccountId, BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
+	/// Current runners-up of a room, best backed first.
+	#[pallet::storage]
+	#[pallet::getter(fn runners_up)]
+	pub type RunnersUp<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Vec<SeatHolder<T::AccountId, BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
+	/// Candidates of the next election of a room and their bonds, sorted by account.
+	#[pallet::storage]
+	#[pallet::getter(fn candidates)]
+	pub type Candidates<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, Vec<(T::AccountId, BalanceOf<T>)>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn voting)]
+	pub type Voting<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		Voter<T::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn voter_count)]
+	pub type VoterCount<T: Config> = StorageMap<_, Blake2_128Concat, RoomIndex, u32, ValueQuery>;
+
+	/// Number of elections held in a room.
+	#[pallet::storage]
+	#[pallet::getter(fn election_rounds)]
+	pub type ElectionRounds<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, u32, ValueQuery>;
+
+	/// Block of the next election of a room.
+	#[pallet::storage]
+	#[pallet::getter(fn next_election)]
+	pub type NextElection<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, T::BlockNumber, OptionQuery>;
+
+	/// Rooms to hold an election in at a given block.
+	#[pallet::storage]
+	#[pallet::getter(fn election_queue)]
+	pub type ElectionQueue<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<RoomIndex>, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Account is not a member of the room
+		NotInRoom,
+		/// Account is already a candidate
+		DuplicatedCandidate,
+		/// Members and runners-up stand again without submitting
+		AlreadySeated,
+		/// There can only be `MaxCandidates` candidates
+		TooManyCandidates,
+		/// Account is neither a candidate, a member nor a runner-up
+		NotCandidate,
+		/// A ballot must approve of at least one candidate
+		NoVotes,
+		/// A ballot can approve of at most `MaxVotesPerVoter` candidates
+		TooManyVotes,
+		/// A ballot can only approve of candidates, members and runners-up
+		InvalidVote,
+		/// The stake is below `VotingBond`
+		LowStake,
+		/// There can only be `MaxVoters` voters
+		TooManyVoters,
+		/// Account has not voted
+		NotVoter,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let mut rooms = <ElectionQueue<T>>::take(n);
+			let limit = T::MaxElectionsPerBlock::get() as usize;
+			if rooms.len() > limit {
+				// the other rooms go ahead of those due in the next block
+				let mut deferred = rooms.split_off(limit);
+				let next = n + One::one();
+				for room_id in deferred.iter() {
+					<NextElection<T>>::insert(room_id, next);
+				}
+				weight = weight
+					.saturating_add(T::DbWeight::get().reads_writes(1, 1 + deferred.len() as u64));
+				<ElectionQueue<T>>::mutate(next, |queue| {
+					deferred.append(queue);
+					*queue = deferred;
+				});
+			}
+			for room_id in rooms {
+				weight = weight.saturating_add(Self::do_phragmen(room_id));
+				weight = weight.saturating_add(Self::schedule_next_election(room_id, n));
+			}
+			weight
+		}
+
+		fn integrity_test() {
+			assert!(T::MaxElectionsPerBlock::get() > 0, "no election would ever be held");
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Approve of candidates of the room, replacing any earlier ballot.
+		///
+		/// `value` is reserved as the weight of the ballot and must be at least `VotingBond`.
+		#[pallet::weight(1500_000_000)]
+		pub fn vote(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			mut votes: Vec<T::AccountId>,
+			#[pallet::compact] value: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(T::ListenHandler::is_in_room(room_id, who.clone())?, Error::<T>::NotInRoom);
+			ensure!(!votes.is_empty(), Error::<T>::NoVotes);
+			ensure!(votes.len() <= T::MaxVotesPerVoter::get() as usize, Error::<T>::TooManyVotes);
+			ensure!(value >= T::VotingBond::get(), Error::<T>::LowStake);
+
+			votes.sort();
+			votes.dedup();
+			ensure!(votes.iter().all(|c| Self::is_standing(room_id, c)), Error::<T>::InvalidVote);
+
+			match Self::voting(room_id, &who) {
+				Some(old) =>
+					if value > old.stake {
+						T::Currency::reserve(&who, value - old.stake)?;
+					} else {
+						T::Currency::unreserve(&who, old.stake - value);
+					},
+				None => {
+					ensure!(
+						Self::voter_count(room_id) < T::MaxVoters::get(),
+						Error::<T>::TooManyVoters
+					);
+					T::Currency::reserve(&who, value)?;
+					<VoterCount<T>>::mutate(room_id, |c| *c += 1);
+				},
+			}
+
+			<Voting<T>>::insert(room_id, &who, Voter { votes: votes.clone(), stake: value });
+			Self::schedule_election(room_id);
+
+			Self::deposit_event(Event::Voted(room_id, who, votes, value));
+			Ok(())
+		}
+
+		/// Withdraw the ballot and unreserve its stake.
+		#[pallet::weight(1500_000_000)]
+		pub fn remove_voter(origin: OriginFor<T>, room_id: RoomIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let voter = <Voting<T>>::take(room_id, &who).ok_or(Error::<T>::NotVoter)?;
+			T::Currency::unreserve(&who, voter.stake);
+			<VoterCount<T>>::mutate(room_id, |c| *c = c.saturating_sub(1));
+
+			Self::deposit_event(Event::VoterRemoved(room_id, who));
+			Ok(())
+		}
+
+		/// Stand as a candidate in the next election of the room, reserving `CandidacyBond`.
+		#[pallet::weight(1500_000_000)]
+		pub fn submit_candidacy(origin: OriginFor<T>, room_id: RoomIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(T::ListenHandler::is_in_room(room_id, who.clone())?, Error::<T>::NotInRoom);
+			ensure!(
+				!Self::members(room_id).iter().any(|m| m.who == who) &&
+					!Self::runners_up(room_id).iter().any(|r| r.who == who),
+				Error::<T>::AlreadySeated
+			);
+
+			let mut candidates = Self::candidates(room_id);
+			let pos = match candidates.binary_search_by(|(c, _)| c.cmp(&who)) {
+				Ok(_) => return Err(Error::<T>::DuplicatedCandidate.into()),
+				Err(pos) => pos,
+			};
+			ensure!(
+				candidates.len() < T::MaxCandidates::get() as usize,
+				Error::<T>::TooManyCandidates
+			);
+
+			let deposit = T::CandidacyBond::get();
+			T::Currency::reserve(&who, deposit)?;
+			candidates.insert(pos, (who.clone(), deposit));
+			<Candidates<T>>::insert(room_id, candidates);
+			Self::schedule_election(room_id);
+
+			Self::deposit_event(Event::CandidateSubmitted(room_id, who));
+			Ok(())
+		}
+
+		/// Withdraw a candidacy, a council seat or a runner-up seat and unreserve its bond.
+		///
+		/// A member who steps down is replaced by the best backed runner-up, and the new council
+		/// is pushed to the room.
+		#[pallet::weight(1500_000_000)]
+		pub fn renounce_candidacy(origin: OriginFor<T>, room_id: RoomIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut candidates = Self::candidates(room_id);
+			let mut members = Self::members(room_id);
+			let mut runners_up = Self::runners_up(room_id);
+			let deposit = if let Ok(pos) = candidates.binary_search_by(|(c, _)| c.cmp(&who)) {
+				let (_, deposit) = candidates.remove(pos);
+				<Candidates<T>>::insert(room_id, candidates);
+				deposit
+			} else if let Some(pos) = members.iter().position(|m| m.who == who) {
+				let member = members.remove(pos);
+				if !runners_up.is_empty() {
+					members.push(runners_up.remove(0));
+				}
+				let council = members.iter().map(|m| m.who.clone()).collect::<Vec<_>>();
+				T::ListenHandler::set_room_council(room_id, council)?;
+				<Members<T>>::insert(room_id, members);
+				<RunnersUp<T>>::insert(room_id, runners_up);
+				member.deposit
+			} else if let Some(pos) = runners_up.iter().position(|r| r.who == who) {
+				let runner_up = runners_up.remove(pos);
+				<RunnersUp<T>>::insert(room_id, runners_up);
+				runner_up.deposit
+			} else {
+				return Err(Error::<T>::NotCandidate.into())
+			};
+			T::Currency::unreserve(&who, deposit);
+
+			Self::deposit_event(Event::Renounced(room_id, who));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		// Whether the account stands in the next election of the room.
+		fn is_standing(room_id: RoomIndex, who: &T::AccountId) -> bool {
+			Self::candidates(room_id).binary_search_by(|(c, _)| c.cmp(who)).is_ok() ||
+				Self::members(room_id).iter().any(|m| &m.who == who) ||
+				Self::runners_up(room_id).iter().any(|r| &r.who == who)
+		}
+
+		fn schedule_election(room_id: RoomIndex) {
+			if <NextElection<T>>::contains_key(room_id) {
+				return
+			}
+			let at = frame_system::Pallet::<T>::block_number() + T::TermDuration::get();
+			<NextElection<T>>::insert(room_id, at);
+			<ElectionQueue<T>>::append(at, room_id);
+		}
+
+		// Keeps electing every `TermDuration` blocks as long as anyone takes part.
+		fn schedule_next_election(room_id: RoomIndex, now: T::BlockNumber) -> Weight {
+			<NextElection<T>>::remove(room_id);
+			if Self::voter_count(room_id) > 0 ||
+				!Self::candidates(room_id).is_empty() ||
+				!Self::members(room_id).is_empty()
+			{
+				let at = now + T::TermDuration::get();
+				<NextElection<T>>::insert(room_id, at);
+				<ElectionQueue<T>>::append(at, room_id);
+			}
+			T::DbWeight::get().reads_writes(3, 3)
+		}
+
+		/// Run the Phragmén tally of a room and push the winners to the room council.
+		fn do_phragmen(room_id: RoomIndex) -> Weight {
+			let desired_members = T::DesiredMembers::get() as usize;
+			let desired_runners_up = T::DesiredRunnersUp::get() as usize;
+
+			let mut standing = Self::candidates(room_id);
+			standing.extend(Self::members(room_id).into_iter().map(|m| (m.who, m.deposit)));
+			standing.extend(Self::runners_up(room_id).into_iter().map(|r| (r.who, r.deposit)));
+			let candidate_ids = standing.iter().map(|(c, _)| c.clone()).collect::<Vec<_>>();
+
+			let total_issuance = T::Currency::total_issuance();
+			let to_votes = |b: BalanceOf<T>| T::CurrencyToVote::to_vote(b, total_issuance);
+			let to_balance = |e: ExtendedBalance| T::CurrencyToVote::to_currency(e, total_issuance);
+
+			let voters = <Voting<T>>::iter_prefix(room_id)
+				.map(|(who, voter)| {
+					let votes = voter
+						.votes
+						.into_iter()
+						.filter(|c| candidate_ids.contains(c))
+						.collect::<Vec<_>>();
+					(who, to_votes(voter.stake), votes)
+				})
+				.collect::<Vec<_>>();
+			let weight = T::DbWeight::get().reads_writes(
+				4u64.saturating_add(voters.len() as u64),
+				4u64.saturating_add(standing.len() as u64),
+			);
+
+			let winners = match seq_phragmen::<T::AccountId, Perbill>(
+				desired_members.saturating_add(desired_runners_up),
+				candidate_ids,
+				voters,
+				None,
+			) {
+				Ok(ElectionResult { winners, .. }) => winners,
+				Err(_) => {
+					Self::deposit_event(Event::ElectionError(room_id));
+					return weight
+				},
+			};
+
+			let mut winners =
+				winners.into_iter().filter(|(_, stake)| !stake.is_zero()).collect::<Vec<_>>();
+			if winners.is_empty() {
+				Self::deposit_event(Event::EmptyTerm(room_id));
+				return weight
+			}
+			winners.sort_by(|a, b| b.1.cmp(&a.1));
+
+			let mut members = winners
+				.into_iter()
+				.map(|(who, stake)| {
+					let deposit = standing
+						.iter()
+						.find(|(c, _)| c == &who)
+						.map(|(_, d)| *d)
+						.unwrap_or_else(Zero::zero);
+					SeatHolder { who, stake: to_balance(stake), deposit }
+				})
+				.collect::<Vec<_>>();
+			let runners_up = members.split_off(desired_members.min(members.len()));
+
+			let council = members.iter().map(|m| m.who.clone()).collect::<Vec<_>>();
+			if T::ListenHandler::set_room_council(room_id, council).is_err() {
+				Self::deposit_event(Event::ElectionError(room_id));
+				return weight
+			}
+
+			// Whoever did not win a seat gets the candidacy bond back.
+			for (who, deposit) in standing {
+				if !members.iter().chain(runners_up.iter()).any(|s| s.who == who) {
+					T::Currency::unreserve(&who, deposit);
+				}
+			}
+
+			let new_members = members.iter().map(|m| (m.who.clone(), m.stake)).collect::<Vec<_>>();
+			<Members<T>>::insert(room_id, members);
+			<RunnersUp<T>>::insert(room_id, runners_up);
+			<Candidates<T>>::remove(room_id);
+			<ElectionRounds<T>>::mutate(room_id, |r| *r = r.saturating_add(1));
+
+			Self::deposit_event(Event::NewTerm(room_id, new_members));
+			weight
+		}
+	}
+
+	impl<T: Config> RoomTreasuryHandler<RoomIndex> for Pallet<T> {
+		/// Return the bonds of the room's candidates, members and runners-up and the stakes of
+		/// its voters, and take the room out of the election queue.
+		fn remove_room_treasury_info(room_id: RoomIndex) -> Weight {
+			let mut released = 0u64;
+			for seat in <Members<T>>::take(room_id).into_iter().chain(<RunnersUp<T>>::take(room_id))
+			{
+				T::Currency::unreserve(&seat.who, seat.deposit);
+				released += 1;
+			}
+			for (who, deposit) in <Candidates<T>>::take(room_id) {
+				T::Currency::unreserve(&who, deposit);
+				released += 1;
+			}
+			for (who, voter) in <Voting<T>>::drain_prefix(room_id) {
+				T::Currency::unreserve(&who, voter.stake);
+				released += 1;
+			}
+			<VoterCount<T>>::remove(room_id);
+			<ElectionRounds<T>>::remove(room_id);
+			if let Some(at) = <NextElection<T>>::take(room_id) {
+				let mut queue = <ElectionQueue<T>>::take(at);
+				queue.retain(|r| *r != room_id);
+				if !queue.is_empty() {
+					<ElectionQueue<T>>::insert(at, queue);
+				}
+			}
+
+			Self::deposit_event(Event::RoomRemoved(room_id));
+			T::DbWeight::get().reads_writes(6 + released, 7 + released)
+		}
+	}
+}
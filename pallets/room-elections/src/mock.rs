@@ -0,0 +1,212 @@
+//! Mocks for the room-elections module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Everything, Hooks, U128CurrencyToVote},
+};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, result};
+
+use crate as room_elections;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u64;
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+thread_local! {
+	pub static ROOMS: RefCell<BTreeMap<RoomIndex, Vec<AccountId>>> = RefCell::new(BTreeMap::new());
+	pub static COUNCILS: RefCell<BTreeMap<RoomIndex, Vec<AccountId>>> = RefCell::new(BTreeMap::new());
+}
+
+pub fn council(room_id: RoomIndex) -> Vec<AccountId> {
+	COUNCILS.with(|c| c.borrow().get(&room_id).cloned().unwrap_or_default())
+}
+
+pub struct ListenHandlerMock;
+impl ListenHandler<RoomIndex, AccountId, DispatchError, u128> for ListenHandlerMock {
+	fn get_room_council(room_id: RoomIndex) -> result::Result<Vec<AccountId>, DispatchError> {
+		Ok(council(room_id))
+	}
+
+	fn get_prime(_room_id: RoomIndex) -> result::Result<Option<AccountId>, DispatchError> {
+		Ok(None)
+	}
+
+	fn get_root(_room_id: RoomIndex) -> result::Result<AccountId, DispatchError> {
+		Err(DispatchError::Other("unused"))
+	}
+
+	fn get_room_free_amount(_room_id: RoomIndex) -> u128 {
+		0
+	}
+
+	fn sub_room_free_amount(
+		_room_id: RoomIndex,
+		_amount: u128,
+	) -> result::Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn add_room_free_amount(
+		_room_id: RoomIndex,
+		_amount: u128,
+	) -> result::Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn exit_room_council(
+		_room_id: RoomIndex,
+		_who: AccountId,
+		_payout: u128,
+	) -> result::Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn is_in_room(room_id: RoomIndex, who: AccountId) -> result::Result<bool, DispatchError> {
+		ROOMS
+			.with(|r| r.borrow().get(&room_id).map(|listeners| listeners.contains(&who)))
+			.ok_or(DispatchError::Other("room does not exist"))
+	}
+
+	fn set_room_council(
+		room_id: RoomIndex,
+		council: Vec<AccountId>,
+	) -> result::Result<(), DispatchError> {
+		COUNCILS.with(|c| c.borrow_mut().insert(room_id, council));
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const CandidacyBond: u64 = 3;
+	pub const VotingBond: u64 = 2;
+	pub const TermDuration: u64 = 5;
+	pub const DesiredMembers: u32 = 2;
+	pub const DesiredRunnersUp: u32 = 1;
+	pub const MaxCandidates: u32 = 4;
+	pub const MaxVoters: u32 = 4;
+	pub const MaxVotesPerVoter: u32 = 3;
+	pub const MaxElectionsPerBlock: u32 = 1;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type CurrencyToVote = U128CurrencyToVote;
+	type ListenHandler = ListenHandlerMock;
+	type CandidacyBond = CandidacyBond;
+	type VotingBond = VotingBond;
+	type TermDuration = TermDuration;
+	type DesiredMembers = DesiredMembers;
+	type DesiredRunnersUp = DesiredRunnersUp;
+	type MaxCandidates = MaxCandidates;
+	type MaxVoters = MaxVoters;
+	type MaxVotesPerVoter = MaxVotesPerVoter;
+	type MaxElectionsPerBlock = MaxElectionsPerBlock;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Elections: room_elections::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const EVE: AccountId = 5;
+
+pub const ROOM: RoomIndex = 1;
+pub const OTHER_ROOM: RoomIndex = 2;
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Elections::on_initialize(System::block_number());
+	}
+}
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 10), (BOB, 20), (CHARLIE, 30), (DAVE, 40), (EVE, 50)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		ROOMS.with(|r| {
+			*r.borrow_mut() = BTreeMap::from([
+				(ROOM, vec![ALICE, BOB, CHARLIE, DAVE, EVE]),
+				(OTHER_ROOM, vec![ALICE, BOB]),
+			])
+		});
+		COUNCILS.with(|c| c.borrow_mut().clear());
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,211 @@
+//! Unit tests for the room-elections module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+#[test]
+fn submit_candidacy_reserves_bond() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Elections::submit_candidacy(Origin::signed(BOB), ROOM));
+		assert_ok!(Elections::submit_candidacy(Origin::signed(ALICE), ROOM));
+
+		assert_eq!(Elections::candidates(ROOM), vec![(ALICE, 3), (BOB, 3)]);
+		assert_eq!(Balances::reserved_balance(ALICE), 3);
+		assert_eq!(Elections::next_election(ROOM), Some(1 + TermDuration::get()));
+		assert_noop!(
+			Elections::submit_candidacy(Origin::signed(ALICE), ROOM),
+			Error::<Runtime>::DuplicatedCandidate
+		);
+		assert_noop!(
+			Elections::submit_candidacy(Origin::signed(6), ROOM),
+			Error::<Runtime>::NotInRoom
+		);
+	});
+}
+
+#[test]
+fn renounce_candidacy_returns_bond() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Elections::submit_candidacy(Origin::signed(ALICE), ROOM));
+		assert_ok!(Elections::renounce_candidacy(Origin::signed(ALICE), ROOM));
+
+		assert!(Elections::candidates(ROOM).is_empty());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_noop!(
+			Elections::renounce_candidacy(Origin::signed(ALICE), ROOM),
+			Error::<Runtime>::NotCandidate
+		);
+	});
+}
+
+#[test]
+fn vote_checks_ballot() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Elections::submit_candidacy(Origin::signed(ALICE), ROOM));
+
+		assert_noop!(
+			Elections::vote(Origin::signed(DAVE), ROOM, vec![], 5),
+			Error::<Runtime>::NoVotes
+		);
+		assert_noop!(
+			Elections::vote(Origin::signed(DAVE), ROOM, vec![BOB], 5),
+			Error::<Runtime>::InvalidVote
+		);
+		assert_noop!(
+			Elections::vote(Origin::signed(DAVE), ROOM, vec![ALICE], 1),
+			Error::<Runtime>::LowStake
+		);
+	});
+}
+
+#[test]
+fn vote_updates_reserved_stake() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Elections::submit_candidacy(Origin::signed(ALICE), ROOM));
+
+		assert_ok!(Elections::vote(Origin::signed(DAVE), ROOM, vec![ALICE], 10));
+		assert_eq!(Balances::reserved_balance(DAVE), 10);
+		assert_ok!(Elections::vote(Origin::signed(DAVE), ROOM, vec![ALICE], 4));
+		assert_eq!(Balances::reserved_balance(DAVE), 4);
+		assert_eq!(Elections::voter_count(ROOM), 1);
+
+		assert_ok!(Elections::remove_voter(Origin::signed(DAVE), ROOM));
+		assert_eq!(Balances::reserved_balance(DAVE), 0);
+		assert_eq!(Elections::voter_count(ROOM), 0);
+	});
+}
+
+#[test]
+fn election_sets_room_council() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Elections::submit_candidacy(Origin::signed(ALICE), ROOM));
+		assert_ok!(Elections::submit_candidacy(Origin::signed(BOB), ROOM));
+		assert_ok!(Elections::submit_candidacy(Origin::signed(CHARLIE), ROOM));
+		assert_ok!(Elections::submit_candidacy(Origin::signed(DAVE), ROOM));
+
+		assert_ok!(Elections::vote(Origin::signed(EVE), ROOM, vec![BOB], 30));
+		assert_ok!(Elections::vote(Origin::signed(DAVE), ROOM, vec![CHARLIE], 20));
+		assert_ok!(Elections::vote(Origin::signed(CHARLIE), ROOM, vec![ALICE], 10));
+
+		run_to_block(1 + TermDuration::get());
+
+		assert_eq!(council(ROOM), vec![BOB, CHARLIE]);
+		assert_eq!(
+			Elections::members(ROOM),
+			vec![
+				SeatHolder { who: BOB, stake: 30, deposit: 3 },
+				SeatHolder { who: CHARLIE, stake: 20, deposit: 3 },
+			]
+		);
+		assert_eq!(
+			Elections::runners_up(ROOM),
+			vec![SeatHolder { who: ALICE, stake: 10, deposit: 3 }]
+		);
+		assert!(Elections::candidates(ROOM).is_empty());
+		// DAVE lost and got the candidacy bond back; only the stake is still reserved.
+		assert_eq!(Balances::reserved_balance(DAVE), 20);
+		assert_eq!(Elections::election_rounds(ROOM), 1);
+		assert_eq!(Elections::next_election(ROOM), Some(1 + 2 * TermDuration::get()));
+		System::assert_last_event(Event::Elections(crate::Event::NewTerm(
+			ROOM,
+			vec![(BOB, 30), (CHARLIE, 20)],
+		)));
+	});
+}
+
+#[test]
+fn election_without_backing_keeps_council() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Elections::submit_candidacy(Origin::signed(ALICE), ROOM));
+
+		run_to_block(1 + TermDuration::get());
+
+		assert!(council(ROOM).is_empty());
+		assert_eq!(Elections::candidates(ROOM), vec![(ALICE, 3)]);
+		System::assert_last_event(Event::Elections(crate::Event::EmptyTerm(ROOM)));
+	});
+}
+
+fn elect_bob_and_charlie() {
+	assert_ok!(Elections::submit_candidacy(Origin::signed(ALICE), ROOM));
+	assert_ok!(Elections::submit_candidacy(Origin::signed(BOB), ROOM));
+	assert_ok!(Elections::submit_candidacy(Origin::signed(CHARLIE), ROOM));
+	assert_ok!(Elections::vote(Origin::signed(EVE), ROOM, vec![BOB], 30));
+	assert_ok!(Elections::vote(Origin::signed(DAVE), ROOM, vec![CHARLIE], 20));
+	assert_ok!(Elections::vote(Origin::signed(CHARLIE), ROOM, vec![ALICE], 10));
+	run_to_block(1 + TermDuration::get());
+	assert_eq!(council(ROOM), vec![BOB, CHARLIE]);
+}
+
+#[test]
+fn renouncing_member_is_replaced_by_runner_up() {
+	ExtBuilder::build().execute_with(|| {
+		elect_bob_and_charlie();
+
+		assert_ok!(Elections::renounce_candidacy(Origin::signed(BOB), ROOM));
+
+		assert_eq!(council(ROOM), vec![CHARLIE, ALICE]);
+		assert_eq!(
+			Elections::members(ROOM),
+			vec![
+				SeatHolder { who: CHARLIE, stake: 20, deposit: 3 },
+				SeatHolder { who: ALICE, stake: 10, deposit: 3 },
+			]
+		);
+		assert!(Elections::runners_up(ROOM).is_empty());
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		System::assert_last_event(Event::Elections(crate::Event::Renounced(ROOM, BOB)));
+
+		// without runners-up, the seat stays empty until the next election
+		assert_ok!(Elections::renounce_candidacy(Origin::signed(CHARLIE), ROOM));
+		assert_eq!(council(ROOM), vec![ALICE]);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 10);
+	});
+}
+
+#[test]
+fn dissolution_returns_bonds_and_stakes() {
+	ExtBuilder::build().execute_with(|| {
+		elect_bob_and_charlie();
+		assert_ok!(Elections::submit_candidacy(Origin::signed(DAVE), ROOM));
+		let next = Elections::next_election(ROOM).unwrap();
+
+		<Elections as RoomTreasuryHandler<RoomIndex>>::remove_room_treasury_info(ROOM);
+
+		for who in [ALICE, BOB, CHARLIE, DAVE, EVE] {
+			assert_eq!(Balances::reserved_balance(who), 0);
+		}
+		assert!(Elections::members(ROOM).is_empty());
+		assert!(Elections::runners_up(ROOM).is_empty());
+		assert!(Elections::candidates(ROOM).is_empty());
+		assert!(Elections::voting(ROOM, EVE).is_none());
+		assert_eq!(Elections::election_rounds(ROOM), 0);
+		assert_eq!(Elections::next_election(ROOM), None);
+		assert!(Elections::election_queue(next).is_empty());
+		System::assert_last_event(Event::Elections(crate::Event::RoomRemoved(ROOM)));
+	});
+}
+
+#[test]
+fn elections_over_the_block_limit_are_deferred() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(Elections::submit_candidacy(Origin::signed(ALICE), ROOM));
+		assert_ok!(Elections::submit_candidacy(Origin::signed(BOB), OTHER_ROOM));
+		let at = 1 + TermDuration::get();
+		assert_eq!(Elections::election_queue(at), vec![ROOM, OTHER_ROOM]);
+
+		run_to_block(at);
+
+		assert_eq!(Elections::next_election(ROOM), Some(at + TermDuration::get()));
+		assert_eq!(Elections::next_election(OTHER_ROOM), Some(at + 1));
+		assert_eq!(Elections::election_queue(at + 1), vec![OTHER_ROOM]);
+
+		run_to_block(at + 1);
+
+		assert_eq!(Elections::next_election(OTHER_ROOM), Some(at + 1 + TermDuration::get()));
+		System::assert_last_event(Event::Elections(crate::Event::EmptyTerm(OTHER_ROOM)));
+	});
+}
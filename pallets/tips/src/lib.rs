@@ -0,0 +1,408 @@
+// Copyright 2021 LISTEN Developer.
+// This file is part of LISTEN.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Room Tips
+//!
+//! Small rewards for helpful room members, paid out of the room's treasury pot without a spend
+//! proposal.
+//!
+//! Any room member can report a tip for a beneficiary with a reason, reserving a deposit that
+//! grows with the length of the reason. Members of the room council then each declare the amount
+//! they think is deserved. Once half of the council has declared, the tip closes after
+//! `TipCountdown` blocks, and anyone can then pay it: the median of the declared amounts is taken
+//! from the room's free amount, the reporter gets `TipFindersFee` of it, the beneficiary gets the
+//! rest, and the deposit is returned.
+//!
+//! When a room is dissolved, its open tips are removed without being paid and their deposits
+//! are returned, for up to `DissolutionLimit` tips right away and as many in every following
+//! block. No tip can be reported, tipped or paid in the room meanwhile.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod mock;
+mod tests;
+
+pub use crate::pallet::*;
+use codec::{Decode, Encode};
+use frame_support::{
+	ensure,
+	traits::{Currency, EnsureOrigin, Get, OnUnbalanced, ReservableCurrency},
+	transactional, BoundedVec,
+};
+use frame_system::ensure_signed;
+use listen_primitives::traits::{ListenHandler, RoomTreasuryHandler};
+use pallet_treasury::RoomIndex;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Hash, Saturating, StaticLookup},
+	Percent, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+/// A tip that has been reported and not paid yet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct OpenTip<AccountId, Balance, BlockNumber, Hash> {
+	/// The hash of the reason for the tip. The reason itself is in `Reasons`, under the hash of
+	/// the tip.
+	pub reason: Hash,
+	/// The account to be tipped.
+	pub who: AccountId,
+	/// The account who reported the tip.
+	pub finder: AccountId,
+	/// The amount held on deposit for the report.
+	pub deposit: Balance,
+	/// The block from which the tip can be paid, once enough council members have tipped.
+	pub closes: Option<BlockNumber>,
+	/// The amounts declared by the council members, ordered by account.
+	pub tips: Vec<(AccountId, Balance)>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	pub type BalanceOf<T> = <<T as pallet_treasury::Config>::NativeCurrency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+	pub type OpenTipOf<T> = OpenTip<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		BlockNumberFor<T>,
+		<T as frame_system::Config>::Hash,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_treasury::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The amount held on deposit for reporting a tip.
+		#[pallet::constant]
+		type TipReportDepositBase: Get<BalanceOf<Self>>;
+		/// The amount held on deposit per byte of a tip's reason.
+		#[pallet::constant]
+		type DataDepositPerByte: Get<BalanceOf<Self>>;
+		/// Blocks between enough council members tipping and the tip being payable.
+		#[pallet::constant]
+		type TipCountdown: Get<Self::BlockNumber>;
+		/// The part of a tip that goes to the reporter.
+		#[pallet::constant]
+		type TipFindersFee: Get<Percent>;
+		/// Maximum length of a tip's reason.
+		#[pallet::constant]
+		type MaximumReasonLength: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	#[pallet::generate_store(pub (super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new tip was reported. \[room_id, tip_hash\]
+		NewTip(RoomIndex, T::Hash),
+		/// Enough council members tipped; the tip can be paid after the countdown.
+		/// \[room_id, tip_hash\]
+		TipClosing(RoomIndex, T::Hash),
+		/// A tip was paid. \[room_id, tip_hash, who, payout\]
+		TipClosed(RoomIndex, T::Hash, T::AccountId, BalanceOf<T>),
+		/// A tip was retracted by its reporter. \[room_id, tip_hash\]
+		TipRetracted(RoomIndex, T::Hash),
+		/// A tip was removed and its deposit slashed. \[room_id, tip_hash, finder, deposit\]
+		TipSlashed(RoomIndex, T::Hash, T::AccountId, BalanceOf<T>),
+		/// A tip of a dissolved room was removed and its deposit returned.
+		/// \[room_id, tip_hash, finder, deposit\]
+		TipRemoved(RoomIndex, T::Hash, T::AccountId, BalanceOf<T>),
+		/// Every open tip of a dissolved room has been removed. \[room_id\]
+		RoomTipsRemoved(RoomIndex),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The reason is too long.
+		ReasonTooBig,
+		/// The tip was already reported.
+		AlreadyKnown,
+		/// No tip with that hash.
+		UnknownTip,
+		/// Only the reporter of the tip can retract it.
+		NotFinder,
+		/// Only room members can report tips.
+		NotInRoom,
+		/// Only members of the room council can tip.
+		NotCouncilMember,
+		/// Not enough council members have tipped.
+		StillOpen,
+		/// The countdown of the tip has not passed yet.
+		Premature,
+		/// The room is being dissolved.
+		RoomDissolving,
+	}
+
+	/// The open tips of the rooms, by the hash of the room, the reason and the beneficiary.
+	#[pallet::storage]
+	#[pallet::getter(fn tips)]
+	pub type Tips<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Twox64Concat,
+		T::Hash,
+		OpenTipOf<T>,
+		OptionQuery,
+	>;
+
+	/// The reasons of the open tips, by room and the hash of the tip.
+	#[pallet::storage]
+	#[pallet::getter(fn reasons)]
+	pub type Reasons<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Twox64Concat,
+		T::Hash,
+		BoundedVec<u8, T::MaximumReasonLength>,
+		OptionQuery,
+	>;
+
+	/// Dissolved rooms whose open tips are still being removed.
+	#[pallet::storage]
+	#[pallet::getter(fn dissolving_rooms)]
+	pub type DissolvingRooms<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, (), OptionQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Remove up to `DissolutionLimit` open tips of a dissolved room.
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			let weight = T::DbWeight::get().reads(1);
+			match <DissolvingRooms<T>>::iter_keys().next() {
+				Some(room_id) => weight.saturating_add(Self::continue_dissolution(
+					room_id,
+					T::DissolutionLimit::get(),
+				)),
+				None => weight,
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Report a tip for `who` in a room, reserving a deposit.
+		#[pallet::weight(1500_000_000)]
+		pub fn report_awesome(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			reason: Vec<u8>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let finder = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::ensure_not_dissolving(room_id)?;
+			let reason: BoundedVec<u8, T::MaximumReasonLength> =
+				reason.try_into().map_err(|_| Error::<T>::ReasonTooBig)?;
+			ensure!(
+				T::ListenHandler::is_in_room(room_id.into(), finder.clone())?,
+				Error::<T>::NotInRoom
+			);
+
+			let reason_hash = T::Hashing::hash(&reason[..]);
+			let hash = T::Hashing::hash_of(&(room_id, &reason_hash, &who));
+			ensure!(!<Tips<T>>::contains_key(room_id, &hash), Error::<T>::AlreadyKnown);
+
+			let deposit = T::TipReportDepositBase::get().saturating_add(
+				T::DataDepositPerByte::get().saturating_mul((reason.len() as u32).into()),
+			);
+			T::NativeCurrency::reserve(&finder, deposit)?;
+
+			<Reasons<T>>::insert(room_id, &hash, &reason);
+			<Tips<T>>::insert(
+				room_id,
+				&hash,
+				OpenTip { reason: reason_hash, who, finder, deposit, closes: None, tips: vec![] },
+			);
+
+			Self::deposit_event(Event::NewTip(room_id, hash));
+			Ok(())
+		}
+
+		/// Retract a tip that has not been paid, returning the deposit. Only its reporter can do
+		/// this.
+		#[pallet::weight(1500_000_000)]
+		pub fn retract_tip(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let tip = Self::tips(room_id, &hash).ok_or(Error::<T>::UnknownTip)?;
+			ensure!(tip.finder == who, Error::<T>::NotFinder);
+
+			T::NativeCurrency::unreserve(&who, tip.deposit);
+			Self::remove_tip(room_id, &hash);
+
+			Self::deposit_event(Event::TipRetracted(room_id, hash));
+			Ok(())
+		}
+
+		/// Declare the amount a tip deserves, replacing any earlier declaration. Only members of
+		/// the room council can do this; the countdown starts once half of them have tipped.
+		#[pallet::weight(1500_000_000)]
+		pub fn tip(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			hash: T::Hash,
+			#[pallet::compact] tip_value: BalanceOf<T>,
+		) -> DispatchResult {
+			let tipper = ensure_signed(origin)?;
+			Self::ensure_not_dissolving(room_id)?;
+			let council = T::ListenHandler::get_room_council(room_id.into())?;
+			ensure!(council.contains(&tipper), Error::<T>::NotCouncilMember);
+
+			let mut tip = Self::tips(room_id, &hash).ok_or(Error::<T>::UnknownTip)?;
+			match tip.tips.binary_search_by_key(&&tipper, |t| &t.0) {
+				Ok(pos) => tip.tips[pos] = (tipper, tip_value),
+				Err(pos) => tip.tips.insert(pos, (tipper, tip_value)),
+			}
+			let closing = tip.closes.is_none() && Self::enough_tippers(&tip, &council);
+			if closing {
+				tip.closes = Some(Self::now().saturating_add(T::TipCountdown::get()));
+			}
+			<Tips<T>>::insert(room_id, &hash, tip);
+
+			if closing {
+				Self::deposit_event(Event::TipClosing(room_id, hash));
+			}
+			Ok(())
+		}
+
+		/// Pay a tip whose countdown has passed. Declarations of accounts that have left the room
+		/// council are dropped before the median is taken, and the payout counts against the
+		/// room's budget.
+		#[pallet::weight(1500_000_000)]
+		#[transactional]
+		pub fn close_tip(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			hash: T::Hash,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			// the room's pot has been swept; the dissolution removes the tip instead
+			Self::ensure_not_dissolving(room_id)?;
+			let mut tip = Self::tips(room_id, &hash).ok_or(Error::<T>::UnknownTip)?;
+			let closes = tip.closes.ok_or(Error::<T>::StillOpen)?;
+			ensure!(Self::now() >= closes, Error::<T>::Premature);
+
+			let council = T::ListenHandler::get_room_council(room_id.into())?;
+			tip.tips.retain(|(tipper, _)| council.contains(tipper));
+			ensure!(Self::enough_tippers(&tip, &council), Error::<T>::StillOpen);
+
+			let mut values: Vec<BalanceOf<T>> = tip.tips.iter().map(|(_, value)| *value).collect();
+			values.sort();
+			let payout = values[values.len() / 2];
+
+			let mut beneficiary_payout = payout;
+			if tip.finder != tip.who {
+				let finders_fee = T::TipFindersFee::get() * payout;
+				beneficiary_payout = payout.saturating_sub(finders_fee);
+				pallet_treasury::Pallet::<T>::spend_from_pot(room_id, &tip.finder, finders_fee)?;
+			}
+			pallet_treasury::Pallet::<T>::spend_from_pot(room_id, &tip.who, beneficiary_payout)?;
+			T::NativeCurrency::unreserve(&tip.finder, tip.deposit);
+			Self::remove_tip(room_id, &hash);
+
+			Self::deposit_event(Event::TipClosed(room_id, hash, tip.who, payout));
+			Ok(())
+		}
+
+		/// Remove a tip, slashing its deposit.
+		#[pallet::weight(1500_000_000)]
+		pub fn slash_tip(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			hash: T::Hash,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			let tip = Self::tips(room_id, &hash).ok_or(Error::<T>::UnknownTip)?;
+			let imbalance = T::NativeCurrency::slash_reserved(&tip.finder, tip.deposit).0;
+			T::OnSlash::on_unbalanced(imbalance);
+			Self::remove_tip(room_id, &hash);
+
+			Self::deposit_event(Event::TipSlashed(room_id, hash, tip.finder, tip.deposit));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn now() -> T::BlockNumber {
+			<frame_system::Pallet<T>>::block_number()
+		}
+
+		// Half of the council, rounded up, must have tipped.
+		fn enough_tippers(tip: &OpenTipOf<T>, council: &[T::AccountId]) -> bool {
+			!tip.tips.is_empty() && tip.tips.len() >= (council.len() + 1) / 2
+		}
+
+		fn remove_tip(room_id: RoomIndex, hash: &T::Hash) {
+			<Tips<T>>::remove(room_id, hash);
+			<Reasons<T>>::remove(room_id, hash);
+		}
+
+		fn ensure_not_dissolving(room_id: RoomIndex) -> DispatchResult {
+			ensure!(
+				!<DissolvingRooms<T>>::contains_key(room_id) &&
+					!pallet_treasury::DissolvingRooms::<T>::contains_key(room_id),
+				Error::<T>::RoomDissolving
+			);
+			Ok(())
+		}
+
+		// Removes at most `limit` open tips of a dissolved room, returning their deposits.
+		fn continue_dissolution(room_id: RoomIndex, limit: u32) -> Weight {
+			let tips: Vec<_> = <Tips<T>>::iter_prefix(room_id).take(limit as usize).collect();
+			let count = tips.len() as u64;
+			for (hash, tip) in tips {
+				T::NativeCurrency::unreserve(&tip.finder, tip.deposit);
+				Self::remove_tip(room_id, &hash);
+				Self::deposit_event(Event::TipRemoved(room_id, hash, tip.finder, tip.deposit));
+			}
+			let mut weight = T::DbWeight::get().reads_writes(2 + 2 * count, 3 * count);
+
+			// Tips are removed as they are visited, so the next call starts from the top.
+			if <Tips<T>>::iter_key_prefix(room_id).next().is_none() {
+				<DissolvingRooms<T>>::remove(room_id);
+				Self::deposit_event(Event::RoomTipsRemoved(room_id));
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			}
+			weight
+		}
+	}
+
+	impl<T: Config> RoomTreasuryHandler<RoomIndex> for Pallet<T> {
+		/// Remove the open tips of the room without paying them, returning their deposits. Up
+		/// to `DissolutionLimit` tips are removed right away and as many in every following
+		/// block, until `RoomTipsRemoved` is emitted.
+		fn remove_room_treasury_info(room_id: RoomIndex) -> Weight {
+			<DissolvingRooms<T>>::insert(room_id, ());
+			T::DbWeight::get()
+				.writes(1)
+				.saturating_add(Self::continue_dissolution(room_id, T::DissolutionLimit::get()))
+		}
+	}
+}
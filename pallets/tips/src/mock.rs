@@ -0,0 +1,242 @@
+//! Mocks for the tips module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, parameter_types, traits::Everything, PalletId};
+use frame_system::{EnsureNever, EnsureRoot};
+use listen_primitives::CurrencyId;
+use pallet_treasury::{DissolutionPolicy, NativeCurrencyOnly, RoomFunds};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError, Permill};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, result};
+
+use crate as tips;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u64;
+pub type Balance = u64;
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+thread_local! {
+	pub static FREE_AMOUNTS: RefCell<BTreeMap<RoomIndex, u128>> = RefCell::new(BTreeMap::new());
+	pub static COUNCILS: RefCell<BTreeMap<RoomIndex, Vec<AccountId>>> = RefCell::new(BTreeMap::new());
+}
+
+pub fn room_free_amount(room_id: RoomIndex) -> u128 {
+	FREE_AMOUNTS.with(|f| f.borrow().get(&room_id).cloned().unwrap_or_default())
+}
+
+pub struct ListenHandlerMock;
+impl ListenHandler<RoomIndex, AccountId, DispatchError, u128> for ListenHandlerMock {
+	fn get_room_council(room_id: RoomIndex) -> result::Result<Vec<AccountId>, DispatchError> {
+		Ok(COUNCILS.with(|c| c.borrow().get(&room_id).cloned().unwrap_or_default()))
+	}
+
+	fn get_prime(_room_id: RoomIndex) -> result::Result<Option<AccountId>, DispatchError> {
+		Ok(None)
+	}
+
+	fn get_root(_room_id: RoomIndex) -> result::Result<AccountId, DispatchError> {
+		Err(DispatchError::Other("unused"))
+	}
+
+	fn get_room_free_amount(room_id: RoomIndex) -> u128 {
+		room_free_amount(room_id)
+	}
+
+	fn sub_room_free_amount(room_id: RoomIndex, amount: u128) -> result::Result<(), DispatchError> {
+		let free = room_free_amount(room_id)
+			.checked_sub(amount)
+			.ok_or(DispatchError::Other("room free amount too low"))?;
+		FREE_AMOUNTS.with(|f| f.borrow_mut().insert(room_id, free));
+		Ok(())
+	}
+
+	fn add_room_free_amount(room_id: RoomIndex, amount: u128) -> result::Result<(), DispatchError> {
+		let free = room_free_amount(room_id).saturating_add(amount);
+		FREE_AMOUNTS.with(|f| f.borrow_mut().insert(room_id, free));
+		Ok(())
+	}
+
+	fn exit_room_council(
+		room_id: RoomIndex,
+		who: AccountId,
+		_payout: u128,
+	) -> result::Result<(), DispatchError> {
+		COUNCILS.with(|c| {
+			if let Some(council) = c.borrow_mut().get_mut(&room_id) {
+				council.retain(|a| a != &who);
+			}
+		});
+		Ok(())
+	}
+
+	fn is_in_room(_room_id: RoomIndex, who: AccountId) -> result::Result<bool, DispatchError> {
+		Ok(who != OUTSIDER)
+	}
+
+	fn set_room_council(
+		room_id: RoomIndex,
+		council: Vec<AccountId>,
+	) -> result::Result<(), DispatchError> {
+		COUNCILS.with(|c| c.borrow_mut().insert(room_id, council));
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = 0;
+	pub const BondInSpendCurrency: bool = false;
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 1;
+	pub const SpendPeriod: u64 = 2;
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxPayoutsPerBlock: u32 = 4;
+	pub const MaxApprovals: u32 = 4;
+	pub const PayApproved: DissolutionPolicy = DissolutionPolicy::PayApproved;
+	pub const ProposalLifetime: u64 = 20;
+	pub const ExpiryFee: Permill = Permill::from_percent(10);
+	pub const ClaimWindow: u64 = 20;
+	pub const DissolvedPotBeneficiary: AccountId = 99;
+	pub const DissolutionLimit: u32 = 4;
+}
+
+impl pallet_treasury::Config for Runtime {
+	type NativeCurrency = Balances;
+	type MultiCurrency = NativeCurrencyOnly<Balances>;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type BondInSpendCurrency = BondInSpendCurrency;
+	type ListenHandler = RoomFunds<Runtime, ListenHandlerMock>;
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type RejectOrigin = EnsureRoot<AccountId>;
+	type Event = Event;
+	type OnSlash = ();
+	type WeightInfo = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type PalletId = TreasuryPalletId;
+	type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+	type MaxApprovals = MaxApprovals;
+	type DissolutionPolicy = PayApproved;
+	type RoomOrigin = EnsureNever<RoomIndex>;
+	type DissolvedPotBeneficiary = DissolvedPotBeneficiary;
+	type DissolutionLimit = DissolutionLimit;
+	type VestedTransfer = ();
+	type ProposalLifetime = ProposalLifetime;
+	type ExpiryFee = ExpiryFee;
+	type ClaimWindow = ClaimWindow;
+}
+
+parameter_types! {
+	pub const TipReportDepositBase: Balance = 2;
+	pub const DataDepositPerByte: Balance = 1;
+	pub const TipCountdown: u64 = 3;
+	pub const TipFindersFee: Percent = Percent::from_percent(20);
+	pub const MaximumReasonLength: u32 = 8;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type TipReportDepositBase = TipReportDepositBase;
+	type DataDepositPerByte = DataDepositPerByte;
+	type TipCountdown = TipCountdown;
+	type TipFindersFee = TipFindersFee;
+	type MaximumReasonLength = MaximumReasonLength;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
+		Tips: tips::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const OUTSIDER: AccountId = 5;
+
+pub const ROOM: RoomIndex = 1;
+
+/// Moves `value` into the pot of `ROOM` from `ALICE`.
+pub fn fund_room(value: Balance) {
+	Treasury::fund_room(Origin::signed(ALICE), ROOM, GetNativeCurrencyId::get(), value).unwrap();
+}
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 100), (BOB, 20), (CHARLIE, 20), (DAVE, 20), (OUTSIDER, 20)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		FREE_AMOUNTS.with(|f| f.borrow_mut().clear());
+		COUNCILS.with(|c| *c.borrow_mut() = BTreeMap::from([(ROOM, vec![ALICE, BOB, CHARLIE])]));
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,193 @@
+//! Unit tests for the tips module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use mock::{Event, Tips, *};
+use sp_core::H256;
+use sp_runtime::traits::{BadOrigin, BlakeTwo256};
+
+fn report(reason: &[u8]) -> H256 {
+	assert_ok!(Tips::report_awesome(Origin::signed(DAVE), ROOM, reason.to_vec(), OUTSIDER));
+	let reason_hash = BlakeTwo256::hash(reason);
+	BlakeTwo256::hash_of(&(ROOM, reason_hash, OUTSIDER))
+}
+
+#[test]
+fn report_awesome_reserves_deposit() {
+	ExtBuilder::build().execute_with(|| {
+		assert_noop!(
+			Tips::report_awesome(Origin::signed(OUTSIDER), ROOM, b"help".to_vec(), DAVE),
+			Error::<Runtime>::NotInRoom
+		);
+		assert_noop!(
+			Tips::report_awesome(Origin::signed(DAVE), ROOM, vec![0; 9], OUTSIDER),
+			Error::<Runtime>::ReasonTooBig
+		);
+
+		let hash = report(b"help");
+		System::assert_last_event(Event::Tips(crate::Event::NewTip(ROOM, hash)));
+		assert_eq!(Balances::reserved_balance(DAVE), 6);
+		assert_eq!(Tips::reasons(ROOM, hash).unwrap().into_inner(), b"help".to_vec());
+		assert_noop!(
+			Tips::report_awesome(Origin::signed(DAVE), ROOM, b"help".to_vec(), OUTSIDER),
+			Error::<Runtime>::AlreadyKnown
+		);
+
+		assert_noop!(
+			Tips::retract_tip(Origin::signed(BOB), ROOM, hash),
+			Error::<Runtime>::NotFinder
+		);
+		assert_ok!(Tips::retract_tip(Origin::signed(DAVE), ROOM, hash));
+		assert_eq!(Balances::reserved_balance(DAVE), 0);
+		assert_eq!(Tips::tips(ROOM, hash), None);
+	});
+}
+
+#[test]
+fn median_tip_is_paid_after_countdown() {
+	ExtBuilder::build().execute_with(|| {
+		fund_room(50);
+		let hash = report(b"help");
+
+		assert_noop!(
+			Tips::tip(Origin::signed(DAVE), ROOM, hash, 10),
+			Error::<Runtime>::NotCouncilMember
+		);
+		assert_ok!(Tips::tip(Origin::signed(ALICE), ROOM, hash, 10));
+		assert_noop!(
+			Tips::close_tip(Origin::signed(DAVE), ROOM, hash),
+			Error::<Runtime>::StillOpen
+		);
+		assert_ok!(Tips::tip(Origin::signed(BOB), ROOM, hash, 20));
+		System::assert_last_event(Event::Tips(crate::Event::TipClosing(ROOM, hash)));
+		assert_ok!(Tips::tip(Origin::signed(CHARLIE), ROOM, hash, 30));
+		assert_noop!(
+			Tips::close_tip(Origin::signed(DAVE), ROOM, hash),
+			Error::<Runtime>::Premature
+		);
+
+		System::set_block_number(1 + TipCountdown::get());
+		assert_ok!(Tips::close_tip(Origin::signed(DAVE), ROOM, hash));
+		System::assert_last_event(Event::Tips(crate::Event::TipClosed(ROOM, hash, OUTSIDER, 20)));
+		assert_eq!(Balances::free_balance(OUTSIDER), 36);
+		assert_eq!(Balances::free_balance(DAVE), 24);
+		assert_eq!(Balances::reserved_balance(DAVE), 0);
+		assert_eq!(room_free_amount(ROOM), 30);
+		assert_eq!(Tips::reasons(ROOM, hash), None);
+	});
+}
+
+#[test]
+fn tips_of_former_council_members_are_dropped() {
+	ExtBuilder::build().execute_with(|| {
+		fund_room(50);
+		let hash = report(b"help");
+		assert_ok!(Tips::tip(Origin::signed(ALICE), ROOM, hash, 10));
+		assert_ok!(Tips::tip(Origin::signed(BOB), ROOM, hash, 20));
+		assert_ok!(ListenHandlerMock::exit_room_council(ROOM, BOB, 0));
+
+		System::set_block_number(1 + TipCountdown::get());
+		assert_noop!(
+			Tips::close_tip(Origin::signed(DAVE), ROOM, hash),
+			Error::<Runtime>::StillOpen
+		);
+	});
+}
+
+#[test]
+fn slash_tip_slashes_deposit() {
+	ExtBuilder::build().execute_with(|| {
+		let hash = report(b"spam");
+		assert_noop!(Tips::slash_tip(Origin::signed(ALICE), ROOM, hash), BadOrigin);
+		assert_ok!(Tips::slash_tip(Origin::root(), ROOM, hash));
+		System::assert_last_event(Event::Tips(crate::Event::TipSlashed(ROOM, hash, DAVE, 6)));
+		assert_eq!(Balances::free_balance(DAVE), 14);
+		assert_eq!(Balances::reserved_balance(DAVE), 0);
+	});
+}
+
+#[test]
+fn tips_with_the_same_reason_keep_it_apart() {
+	ExtBuilder::build().execute_with(|| {
+		let hash = report(b"help");
+		assert_ok!(Tips::report_awesome(Origin::signed(DAVE), ROOM, b"help".to_vec(), BOB));
+		let other = BlakeTwo256::hash_of(&(ROOM, BlakeTwo256::hash(b"help"), BOB));
+
+		assert_ok!(Tips::retract_tip(Origin::signed(DAVE), ROOM, hash));
+		assert_eq!(Tips::reasons(ROOM, hash), None);
+		assert_eq!(Tips::reasons(ROOM, other).unwrap().into_inner(), b"help".to_vec());
+	});
+}
+
+#[test]
+fn dissolution_returns_the_deposits_of_open_tips() {
+	ExtBuilder::build().execute_with(|| {
+		fund_room(50);
+		let hash = report(b"help");
+		assert_ok!(Tips::tip(Origin::signed(ALICE), ROOM, hash, 10));
+
+		Tips::remove_room_treasury_info(ROOM);
+		System::assert_has_event(Event::Tips(crate::Event::TipRemoved(ROOM, hash, DAVE, 6)));
+		System::assert_last_event(Event::Tips(crate::Event::RoomTipsRemoved(ROOM)));
+		assert_eq!(Tips::tips(ROOM, hash), None);
+		assert_eq!(Tips::reasons(ROOM, hash), None);
+		assert_eq!(Balances::reserved_balance(DAVE), 0);
+		assert_eq!(Balances::free_balance(OUTSIDER), 20);
+		assert_eq!(room_free_amount(ROOM), 50);
+	});
+}
+
+#[test]
+fn dissolution_removes_tips_over_several_blocks() {
+	ExtBuilder::build().execute_with(|| {
+		for who in [ALICE, BOB, CHARLIE, DAVE, OUTSIDER] {
+			assert_ok!(Tips::report_awesome(Origin::signed(ALICE), ROOM, vec![], who));
+		}
+		assert_eq!(Balances::reserved_balance(ALICE), 10);
+
+		Tips::remove_room_treasury_info(ROOM);
+		// `DissolutionLimit` is four, so one tip waits for the next block.
+		assert_eq!(Balances::reserved_balance(ALICE), 2);
+		assert_eq!(Tips::dissolving_rooms(ROOM), Some(()));
+		assert_noop!(
+			Tips::report_awesome(Origin::signed(DAVE), ROOM, b"help".to_vec(), OUTSIDER),
+			Error::<Runtime>::RoomDissolving
+		);
+
+		System::set_block_number(2);
+		Tips::on_initialize(2);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Reasons::<Runtime>::iter_prefix(ROOM).count(), 0);
+		assert_eq!(Tips::dissolving_rooms(ROOM), None);
+		System::assert_last_event(Event::Tips(crate::Event::RoomTipsRemoved(ROOM)));
+	});
+}
+
+#[test]
+fn tips_count_against_the_room_budget() {
+	ExtBuilder::build().execute_with(|| {
+		fund_room(50);
+		pallet_treasury::RoomBudgets::<Runtime>::insert(
+			ROOM,
+			pallet_treasury::RoomBudget { max_spend: Some(15), burn: None },
+		);
+		let hash = report(b"help");
+		assert_ok!(Tips::tip(Origin::signed(ALICE), ROOM, hash, 20));
+		assert_ok!(Tips::tip(Origin::signed(BOB), ROOM, hash, 20));
+
+		System::set_block_number(1 + TipCountdown::get());
+		assert_noop!(
+			Tips::close_tip(Origin::signed(DAVE), ROOM, hash),
+			pallet_treasury::Error::<Runtime>::BudgetExceeded
+		);
+
+		pallet_treasury::RoomBudgets::<Runtime>::insert(
+			ROOM,
+			pallet_treasury::RoomBudget { max_spend: Some(20), burn: None },
+		);
+		assert_ok!(Tips::close_tip(Origin::signed(DAVE), ROOM, hash));
+		assert_eq!(Treasury::period_spending(ROOM).1, 20);
+	});
+}
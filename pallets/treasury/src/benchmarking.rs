@@ -21,80 +21,94 @@
 
 use super::*;
 
-use frame_benchmarking::{account, benchmarks_instance, impl_benchmark_test_suite};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
 use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
 
-use crate::Module as Treasury;
+use crate::Pallet as Treasury;
 
 const SEED: u32 = 0;
 
 // Create the pre-requisite information needed to create a treasury `propose_spend`.
-fn setup_proposal<T: Config<I>, I: Instance>(
+fn setup_proposal<T: Config>(
 	u: u32,
-) -> (T::AccountId, BalanceOf<T, I>, <T::Lookup as StaticLookup>::Source) {
+) -> (T::AccountId, BalanceOf<T>, <T::Lookup as StaticLookup>::Source) {
 	let caller = account("caller", u, SEED);
-	let value: BalanceOf<T, I> = T::ProposalBondMinimum::get().saturating_mul(100u32.into());
-	let _ = T::Currency::make_free_balance_be(&caller, value);
+	let value: BalanceOf<T> = T::ProposalBondMinimum::get().saturating_mul(100u32.into());
+	let _ = T::NativeCurrency::make_free_balance_be(&caller, value);
 	let beneficiary = account("beneficiary", u, SEED);
 	let beneficiary_lookup = T::Lookup::unlookup(beneficiary);
 	(caller, value, beneficiary_lookup)
 }
 
-// Create proposals that are approved for use in `on_initialize`.
-fn create_approved_proposals<T: Config<I>, I: Instance>(n: u32) -> Result<(), &'static str> {
+fn propose<T: Config>(u: u32, room_id: RoomIndex) -> Result<ProposalIndex, &'static str> {
+	let (caller, value, lookup) = setup_proposal::<T>(u);
+	Treasury::<T>::propose_spend(
+		RawOrigin::Signed(caller).into(),
+		room_id,
+		T::GetNativeCurrencyId::get(),
+		value,
+		lookup,
+		None,
+	)?;
+	Ok(Treasury::<T>::proposal_count(room_id) - 1)
+}
+
+// Create proposals that are approved for use in `on_initialize`, one per room.
+fn create_approved_proposals<T: Config>(n: u32) -> Result<(), &'static str> {
 	for i in 0..n {
-		let (caller, value, lookup) = setup_proposal::<T, I>(i);
-		Treasury::<T, I>::propose_spend(RawOrigin::Signed(caller).into(), value, lookup)?;
-		let proposal_id = <ProposalCount<I>>::get() - 1;
-		Treasury::<T, I>::approve_proposal(RawOrigin::Root.into(), proposal_id)?;
+		let room_id = i as RoomIndex;
+		setup_pot_account::<T>(room_id);
+		let proposal_id = propose::<T>(i, room_id)?;
+		Treasury::<T>::approve_proposal(
+			T::ApproveOrigin::successful_origin(),
+			room_id,
+			proposal_id,
+		)?;
 	}
-	ensure!(<Approvals<I>>::get().len() == n as usize, "Not all approved");
 	Ok(())
 }
 
-fn setup_pot_account<T: Config<I>, I: Instance>() {
-	let pot_account = Treasury::<T, I>::account_id();
-	let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000u32.into());
-	let _ = T::Currency::make_free_balance_be(&pot_account, value);
+fn setup_pot_account<T: Config>(room_id: RoomIndex) {
+	let pot_account = Treasury::<T>::room_account_id(room_id);
+	let value = T::NativeCurrency::minimum_balance().saturating_mul(1_000_000_000u32.into());
+	let _ = T::NativeCurrency::make_free_balance_be(&pot_account, value);
+	let _ = T::ListenHandler::add_room_free_amount(room_id.into(), value.saturated_into::<u128>());
 }
 
-benchmarks_instance! {
-
+benchmarks! {
 	propose_spend {
-		let (caller, value, beneficiary_lookup) = setup_proposal::<T, _>(SEED);
+		let (caller, value, beneficiary_lookup) = setup_proposal::<T>(SEED);
 		// Whitelist caller account from further DB operations.
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
-	}: _(RawOrigin::Signed(caller), value, beneficiary_lookup)
+	}: _(
+		RawOrigin::Signed(caller),
+		Default::default(),
+		T::GetNativeCurrencyId::get(),
+		value,
+		beneficiary_lookup,
+		None
+	)
 
 	reject_proposal {
-		let (caller, value, beneficiary_lookup) = setup_proposal::<T, _>(SEED);
-		Treasury::<T, _>::propose_spend(
-			RawOrigin::Signed(caller).into(),
-			value,
-			beneficiary_lookup
-		)?;
-		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
-	}: _(RawOrigin::Root, proposal_id)
+		let proposal_id = propose::<T>(SEED, Default::default())?;
+	}: _<T::Origin>(T::RejectOrigin::successful_origin(), Default::default(), proposal_id)
 
 	approve_proposal {
-		let (caller, value, beneficiary_lookup) = setup_proposal::<T, _>(SEED);
-		Treasury::<T, _>::propose_spend(
-			RawOrigin::Signed(caller).into(),
-			value,
-			beneficiary_lookup
-		)?;
-		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
-	}: _(RawOrigin::Root, proposal_id)
+		let proposal_id = propose::<T>(SEED, Default::default())?;
+	}: _<T::Origin>(T::ApproveOrigin::successful_origin(), Default::default(), proposal_id)
 
 	on_initialize_proposals {
-		let p in 0 .. 100;
-		setup_pot_account::<T, _>();
-		create_approved_proposals::<T, _>(p)?;
+		let p in 0 .. T::MaxPayoutsPerBlock::get();
+		create_approved_proposals::<T>(p)?;
+		// the approvals mature and a payout round starts
+		let now = Treasury::<T>::now() + T::SpendPeriod::get();
+		frame_system::Pallet::<T>::set_block_number(now);
+		<PayoutRound<T>>::put(None::<(RoomIndex, u32)>);
 	}: {
-		Treasury::<T, _>::on_initialize(T::BlockNumber::zero());
+		Treasury::<T>::on_initialize(now);
 	}
 }
 
-impl_benchmark_test_suite!(Treasury, crate::tests::new_test_ext(), crate::tests::Test,);
+impl_benchmark_test_suite!(Treasury, crate::mock::ExtBuilder::build(), crate::mock::Runtime);
@@ -18,23 +18,38 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod benchmarking;
-#[cfg(test)]
+mod mock;
 mod tests;
 
+pub mod migrations;
 pub mod weights;
 
 pub use crate::pallet::*;
 use codec::{Decode, Encode};
 use frame_support::{
 	ensure,
-	traits::{Currency, EnsureOrigin, Get, OnUnbalanced, ReservableCurrency},
+	traits::{
+		BalanceStatus, Currency, EnsureOrigin, ExistenceRequirement, Get, OnUnbalanced,
+		ReservableCurrency, WithdrawReasons,
+	},
+	transactional, PalletId,
 };
 use frame_system::ensure_signed;
-use listen_primitives::traits::{ListenHandler, RoomTreasuryHandler};
+use listen_primitives::{
+	traits::{
+		ListenHandler, MultiCurrencyListenHandler, PendingSpends, RoomTreasuryHandler,
+		VestedTransfer,
+	},
+	CurrencyId,
+};
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::{traits::StaticLookup, Permill, RuntimeDebug, SaturatedConversion};
+use sp_runtime::{
+	traits::{AccountIdConversion, CheckedSub, Saturating, StaticLookup, Zero},
+	DispatchError, DispatchResult, Permill, RuntimeDebug, SaturatedConversion,
+};
 use sp_std::prelude::*;
 pub use weights::WeightInfo;
 
@@ -54,6 +69,53 @@ pub struct RoomTreasuryProposal<AccountId, Balance, BlockNumber> {
 	/// The amount held on deposit (reserved) for making this proposal.
 	bond: Balance,
 	start_spend_time: Option<BlockNumber>,
+	/// The currency the value is paid in.
+	currency_id: CurrencyId,
+	/// The currency the bond is held in.
+	bond_currency_id: CurrencyId,
+}
+
+/// How an approved spend is streamed to its beneficiary instead of being paid at once.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PayoutSchedule<BlockNumber> {
+	/// Blocks between the spend maturing and the vesting starting.
+	pub start_offset: BlockNumber,
+	/// Blocks between two releases.
+	pub period: BlockNumber,
+	/// Number of releases; the value is split evenly between them.
+	pub period_count: u32,
+}
+
+/// A matured spend that is being vested to its beneficiary.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VestingGrant<AccountId, Balance, BlockNumber> {
+	pub beneficiary: AccountId,
+	pub start: BlockNumber,
+	pub period: BlockNumber,
+	pub period_count: u32,
+	pub per_period: Balance,
+}
+
+/// The spending limits of a room, in its native currency.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RoomBudget<Balance> {
+	/// The most the room can pay out per `SpendPeriod`.
+	pub max_spend: Option<Balance>,
+	/// The part of the room's free amount that its approvals waiting to be paid do not need
+	/// that is burnt at the end of every `SpendPeriod`.
+	pub burn: Option<Permill>,
+}
+
+/// What happens to the approved proposals of a room when it is dissolved.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum DissolutionPolicy {
+	/// Pay them from the room's remaining funds, even if their spend period has not passed.
+	PayApproved,
+	/// Drop them without paying, returning their bonds.
+	RefundApproved,
 }
 
 #[frame_support::pallet]
@@ -62,7 +124,10 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
-	type BalanceOf<T> = <<T as Config>::NativeCurrency as Currency<
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	pub(crate) type BalanceOf<T> = <<T as Config>::NativeCurrency as Currency<
 		<T as frame_system::Config>::AccountId,
 	>>::Balance;
 	pub type NegativeImbalanceOf<T> = <<T as Config>::NativeCurrency as Currency<
@@ -73,7 +138,25 @@ pub mod pallet {
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config: frame_system::Config {
 		type NativeCurrency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
-		type ListenHandler: ListenHandler<u64, Self::AccountId, DispatchError, u128>;
+		/// The currencies rooms hold and spend, the native one included.
+		type MultiCurrency: MultiReservableCurrency<
+			Self::AccountId,
+			CurrencyId = CurrencyId,
+			Balance = BalanceOf<Self>,
+		>;
+		/// The id of the native currency in `MultiCurrency`.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+		/// Whether bonds are held in the currency of the spend rather than the native one.
+		#[pallet::constant]
+		type BondInSpendCurrency: Get<bool>;
+		type ListenHandler: MultiCurrencyListenHandler<
+			u64,
+			Self::AccountId,
+			CurrencyId,
+			DispatchError,
+			u128,
+		>;
 		/// Origin from which approvals must come.
 		type ApproveOrigin: EnsureOrigin<Self::Origin>;
 		/// Origin from which rejections must come.
@@ -96,11 +179,49 @@ pub mod pallet {
 		#[pallet::constant]
 		/// Period between successive spends.
 		type SpendPeriod: Get<Self::BlockNumber>;
+		#[pallet::constant]
+		/// The treasury's pallet id, used for deriving the pot account of every room.
+		type PalletId: Get<PalletId>;
+		#[pallet::constant]
+		/// Maximum number of approved proposals looked at per block when paying out spends.
+		type MaxPayoutsPerBlock: Get<u32>;
+		#[pallet::constant]
+		/// Maximum number of approved proposals waiting to be paid in a room.
+		type MaxApprovals: Get<u32>;
+		/// What happens to the approved proposals of a room when it is dissolved, unless the
+		/// room chose otherwise.
+		type DissolutionPolicy: Get<DissolutionPolicy>;
+		/// Origin of the governance of a room, e.g. a majority of its council. Yields the room.
+		type RoomOrigin: EnsureOrigin<Self::Origin, Success = RoomIndex>;
+		/// Receives what is left in the pot of a dissolved room.
+		type DissolvedPotBeneficiary: Get<Self::AccountId>;
+		/// Maximum number of storage items removed per block while a dissolved room is cleaned
+		/// up.
+		#[pallet::constant]
+		type DissolutionLimit: Get<u32>;
+		/// Blocks after which a proposal that has not been approved expires.
+		#[pallet::constant]
+		type ProposalLifetime: Get<Self::BlockNumber>;
+		/// Part of the bond of an expired proposal that is slashed; the rest is returned.
+		#[pallet::constant]
+		type ExpiryFee: Get<Permill>;
+		/// Blocks after maturing within which an approved spend must be paid, or it lapses.
+		#[pallet::constant]
+		type ClaimWindow: Get<Self::BlockNumber>;
+		/// Pays the spends that carry a payout schedule. Such spends must be in the native
+		/// currency.
+		type VestedTransfer: VestedTransfer<
+			Self::AccountId,
+			Self::BlockNumber,
+			BalanceOf<Self>,
+			DispatchError,
+		>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	#[pallet::generate_store(pub (super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::event]
@@ -108,20 +229,50 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		/// New proposal. \[proposal_index\]
 		Proposed(ProposalIndex),
-		/// We have ended a spend period and will now allocate funds. \[budget_remaining\]
-		Spending(BalanceOf<T>),
-		/// Some funds have been allocated. \[proposal_index, award, beneficiary\]
-		Awarded(ProposalIndex, BalanceOf<T>, T::AccountId),
+		/// We will now allocate the funds of a room in a currency its approvals are paid in.
+		/// \[room_id, currency_id, budget_remaining\]
+		Spending(RoomIndex, CurrencyId, BalanceOf<T>),
+		/// Some funds of a room have been allocated.
+		/// \[room_id, proposal_index, currency_id, award, beneficiary\]
+		Awarded(RoomIndex, ProposalIndex, CurrencyId, BalanceOf<T>, T::AccountId),
 		/// A proposal was rejected; funds were slashed. \[proposal_index, slashed\]
 		Rejected(ProposalIndex, BalanceOf<T>),
-		/// Some of our funds have been burnt. \[burn\]
-		Burnt(BalanceOf<T>),
-		/// Spending has finished; this is the amount that rolls over until next spend.
-		/// \[budget_remaining\]
-		Rollover(BalanceOf<T>),
-		/// Some funds have been deposited. \[deposit\]
-		Deposit(BalanceOf<T>),
+		/// Some of the idle funds of a room have been burnt. \[room_id, burn\]
+		Burnt(RoomIndex, BalanceOf<T>),
+		/// Spending of a room has finished; this is the amount of a currency its approvals are
+		/// paid in that rolls over until next spend. \[room_id, currency_id, budget_remaining\]
+		Rollover(RoomIndex, CurrencyId, BalanceOf<T>),
+		/// Some funds have been deposited in the pot of a room.
+		/// \[who, room_id, currency_id, deposit\]
+		Deposit(T::AccountId, RoomIndex, CurrencyId, BalanceOf<T>),
 		SpendFund(T::AccountId, RoomIndex),
+		/// The bond of a proposal that was not approved was returned when its room was
+		/// dissolved. \[room_id, proposal_index, bond\]
+		BondReleased(RoomIndex, ProposalIndex, BalanceOf<T>),
+		/// An approved proposal was dropped without payment when its room was dissolved, and its
+		/// bond returned. \[room_id, proposal_index, bond\]
+		SpendRefunded(RoomIndex, ProposalIndex, BalanceOf<T>),
+		/// A vesting grant was cancelled and its unvested funds returned to the room's pot.
+		/// \[room_id, proposal_index, returned\]
+		GrantCancelled(RoomIndex, ProposalIndex, BalanceOf<T>),
+		/// A fully vested grant was dropped. \[room_id, proposal_index\]
+		GrantPruned(RoomIndex, ProposalIndex),
+		/// The budget of a room was set. \[room_id, max_spend, burn\]
+		BudgetSet(RoomIndex, Option<BalanceOf<T>>, Option<Permill>),
+		/// A proposal was not approved within its lifetime; its bond was returned minus the
+		/// expiry fee. \[room_id, proposal_index, fee\]
+		ProposalExpired(RoomIndex, ProposalIndex, BalanceOf<T>),
+		/// An approved spend was not paid within its claim window and its funds stay with the
+		/// room. \[room_id, proposal_index\]
+		SpendLapsed(RoomIndex, ProposalIndex),
+		/// A room chose what happens to its approved proposals when it is dissolved.
+		/// \[room_id, policy\]
+		DissolutionPolicySet(RoomIndex, Option<DissolutionPolicy>),
+		/// What was left in the pot of a dissolved room was moved to `DissolvedPotBeneficiary`.
+		/// \[room_id, currency_id, amount\]
+		PotSwept(RoomIndex, CurrencyId, BalanceOf<T>),
+		/// The treasury storage of a dissolved room has been removed. \[room_id\]
+		RoomDissolved(RoomIndex),
 	}
 
 	/// Number of proposals that have been made.
@@ -145,8 +296,110 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn approvals)]
-	pub type Approvals<T: Config> =
-		StorageMap<_, Blake2_128Concat, RoomIndex, Vec<ProposalIndex>, ValueQuery>;
+	pub type Approvals<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		BoundedVec<ProposalIndex, T::MaxApprovals>,
+		ValueQuery,
+	>;
+
+	/// The payout schedules of the proposals that are streamed once they mature.
+	#[pallet::storage]
+	#[pallet::getter(fn spend_schedules)]
+	pub type SpendSchedules<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		PayoutSchedule<T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The vesting grants of paid scheduled spends, kept so that they can be cancelled.
+	#[pallet::storage]
+	#[pallet::getter(fn grants)]
+	pub type Grants<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		VestingGrant<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// When each proposal was made.
+	#[pallet::storage]
+	#[pallet::getter(fn proposed_at)]
+	pub type ProposedAt<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		ProposalIndex,
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// The raw key of the last proposal looked at by the expiry sweep.
+	#[pallet::storage]
+	#[pallet::getter(fn expiry_cursor)]
+	pub type ExpiryCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// The budgets of the rooms that have one.
+	#[pallet::storage]
+	#[pallet::getter(fn room_budgets)]
+	pub type RoomBudgets<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, RoomBudget<BalanceOf<T>>, OptionQuery>;
+
+	/// What a room has paid out in its native currency, with the start of the `SpendPeriod` it
+	/// was paid in. Only kept for rooms with a maximum spend.
+	#[pallet::storage]
+	#[pallet::getter(fn period_spending)]
+	pub type PeriodSpending<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, (T::BlockNumber, BalanceOf<T>), ValueQuery>;
+
+	/// The burn round in progress, if any, with the last room that was looked at. It starts
+	/// once the payout round of the period has finished.
+	#[pallet::storage]
+	#[pallet::getter(fn burn_round)]
+	pub type BurnRound<T: Config> = StorageValue<_, Option<RoomIndex>, OptionQuery>;
+
+	/// The payout round in progress, if any, with the room it is in and how many of that room's
+	/// approvals it has looked at. `Some(None)` means that the round has not looked at any room
+	/// yet.
+	#[pallet::storage]
+	#[pallet::getter(fn payout_round)]
+	pub type PayoutRound<T: Config> = StorageValue<_, Option<(RoomIndex, u32)>, OptionQuery>;
+
+	/// The free amounts of rooms in currencies other than the native one, for runtimes that
+	/// use `RoomFunds`.
+	#[pallet::storage]
+	#[pallet::getter(fn room_currency_funds)]
+	pub type RoomCurrencyFunds<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoomIndex,
+		Blake2_128Concat,
+		CurrencyId,
+		u128,
+		ValueQuery,
+	>;
+
+	/// The dissolution policy of the rooms that chose their own.
+	#[pallet::storage]
+	#[pallet::getter(fn room_dissolution_policy)]
+	pub type RoomDissolutionPolicies<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, DissolutionPolicy, OptionQuery>;
+
+	/// Dissolved rooms whose storage is still being removed, with the index of the map being
+	/// cleared and the cursor to continue from.
+	#[pallet::storage]
+	#[pallet::getter(fn dissolving_rooms)]
+	pub type DissolvingRooms<T: Config> =
+		StorageMap<_, Blake2_128Concat, RoomIndex, (u8, Option<Vec<u8>>), OptionQuery>;
 
 	#[pallet::error]
 	pub enum Error<T> {
@@ -155,22 +408,129 @@ pub mod pallet {
 		/// No proposal or bounty at that index.
 		InvalidIndex,
 		RoomHaveNoProposal,
+		/// The room's pot can not cover the spend.
+		InsufficientRoomFunds,
+		/// There can only be `MaxApprovals` approved proposals waiting in a room.
+		TooManyApprovals,
+		/// The proposal has not been approved.
+		NotApproved,
+		/// The spend period of the proposal has not passed yet.
+		NotMatured,
+		/// Only the beneficiary or the proposer can claim a spend.
+		NotBeneficiaryOrProposer,
+		/// A payout schedule needs a non-zero period and period count that evenly divides the
+		/// value.
+		InvalidSchedule,
+		/// Only spends in the native currency can carry a payout schedule.
+		ScheduleNotNative,
+		/// There is no vesting grant for that proposal.
+		NoGrant,
+		/// The spend does not fit in the room's budget for the period.
+		BudgetExceeded,
+		/// The proposal has already been approved.
+		AlreadyApproved,
+		/// The room is being dissolved.
+		RoomDissolving,
+		/// The vesting grant still has funds to release.
+		GrantNotVested,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Start a payout round every `SpendPeriod` and continue the round in progress, paying
+		/// at most `MaxPayoutsPerBlock` approvals per block. Once it is done, burn the idle funds
+		/// of at most `MaxPayoutsPerBlock` rooms per block. Dissolved rooms are cleaned up
+		/// alongside.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads(2);
+			if let Some(room_id) = <DissolvingRooms<T>>::iter_keys().next() {
+				weight = weight.saturating_add(Self::continue_dissolution(
+					room_id,
+					T::DissolutionLimit::get(),
+				));
+			}
+			let period = T::SpendPeriod::get();
+			if !period.is_zero() && (n % period).is_zero() {
+				<PayoutRound<T>>::put(None::<(RoomIndex, u32)>);
+				<BurnRound<T>>::put(None::<RoomIndex>);
+				weight = weight.saturating_add(T::DbWeight::get().writes(2));
+			}
+			match Self::payout_round() {
+				Some(cursor) => weight.saturating_add(Self::continue_payouts(cursor)),
+				None => {
+					weight = weight.saturating_add(T::DbWeight::get().reads(1));
+					match Self::burn_round() {
+						Some(cursor) => weight.saturating_add(Self::continue_burns(cursor)),
+						None => weight,
+					}
+				},
+			}
+		}
+
+		/// Expire and lapse proposals with the weight left in the block.
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::sweep_expired(remaining_weight)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		fn integrity_test() {
+			assert!(!T::SpendPeriod::get().is_zero(), "`SpendPeriod` must not be zero");
+			assert!(T::MaxPayoutsPerBlock::get() > 0, "`MaxPayoutsPerBlock` must not be zero");
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		/// Propose to pay `value` of `currency_id` from the room's pot to `beneficiary`.
+		///
+		/// The bond is held in the native currency, or in `currency_id` if `BondInSpendCurrency`.
+		/// With a `schedule`, the value is vested to the beneficiary once the spend matures.
 		#[pallet::weight(1500_000_000)]
 		pub fn propose_spend(
 			origin: OriginFor<T>,
 			room_id: RoomIndex,
+			currency_id: CurrencyId,
 			#[pallet::compact] value: BalanceOf<T>,
 			beneficiary: <T::Lookup as StaticLookup>::Source,
+			schedule: Option<PayoutSchedule<T::BlockNumber>>,
 		) -> DispatchResult {
 			let proposer = ensure_signed(origin)?;
 			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(!<DissolvingRooms<T>>::contains_key(room_id), Error::<T>::RoomDissolving);
+			if let Some(schedule) = &schedule {
+				ensure!(
+					currency_id == T::GetNativeCurrencyId::get(),
+					Error::<T>::ScheduleNotNative
+				);
+				ensure!(
+					!schedule.period.is_zero() &&
+						schedule.period_count != 0 &&
+						(value % BalanceOf::<T>::from(schedule.period_count)).is_zero(),
+					Error::<T>::InvalidSchedule
+				);
+				// the vesting starts no earlier than this once the spend is approved
+				let start = Self::now()
+					.saturating_add(T::SpendPeriod::get())
+					.saturating_add(schedule.start_offset);
+				T::VestedTransfer::ensure_can_vested_transfer(
+					&beneficiary,
+					start,
+					schedule.period,
+					schedule.period_count,
+					value / BalanceOf::<T>::from(schedule.period_count),
+				)?;
+			}
 
 			let bond = Self::calculate_bond(value);
-			T::NativeCurrency::reserve(&proposer, bond)
+			let bond_currency_id = if T::BondInSpendCurrency::get() {
+				currency_id
+			} else {
+				T::GetNativeCurrencyId::get()
+			};
+			T::MultiCurrency::reserve(bond_currency_id, &proposer, bond)
 				.map_err(|_| Error::<T>::InsufficientProposersBalance)?;
 
 			let c = Self::proposal_count(room_id);
@@ -179,8 +539,20 @@ pub mod pallet {
 			<Proposals<T>>::insert(
 				room_id,
 				c,
-				RoomTreasuryProposal { proposer, value, beneficiary, bond, start_spend_time },
+				RoomTreasuryProposal {
+					proposer,
+					value,
+					beneficiary,
+					bond,
+					start_spend_time,
+					currency_id,
+					bond_currency_id,
+				},
 			);
+			<ProposedAt<T>>::insert(room_id, c, Self::now());
+			if let Some(schedule) = schedule {
+				<SpendSchedules<T>>::insert(room_id, c, schedule);
+			}
 
 			Self::deposit_event(Event::Proposed(c));
 			Ok(())
@@ -194,11 +566,10 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::RejectOrigin::ensure_origin(origin)?;
 
-			let proposal =
-				<Proposals<T>>::take(room_id, &proposal_id).ok_or(Error::<T>::InvalidIndex)?;
+			let proposal = Self::proposals(room_id, proposal_id).ok_or(Error::<T>::InvalidIndex)?;
+			Self::remove_proposal(room_id, proposal_id);
 			let value = proposal.bond;
-			let imbalance = T::NativeCurrency::slash_reserved(&proposal.proposer, value).0;
-			T::OnSlash::on_unbalanced(imbalance);
+			Self::slash_bond(&proposal, value);
 
 			Self::deposit_event(Event::<T>::Rejected(proposal_id, value));
 			Ok(())
@@ -212,15 +583,34 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ApproveOrigin::ensure_origin(origin)?;
 
-			ensure!(<Proposals<T>>::contains_key(room_id, proposal_id), Error::<T>::InvalidIndex);
+			let proposal = Self::proposals(room_id, proposal_id).ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(proposal.start_spend_time.is_none(), Error::<T>::AlreadyApproved);
+			ensure!(!<DissolvingRooms<T>>::contains_key(room_id), Error::<T>::RoomDissolving);
+			let start_spend_time = Self::now().saturating_add(T::SpendPeriod::get());
+			if let Some(max_spend) = Self::max_spend(room_id) {
+				if proposal.currency_id == T::GetNativeCurrencyId::get() {
+					// the approvals that mature in the same period are paid in the same round, so
+					// they must fit in that period's budget together
+					let period_start = Self::period_start(start_spend_time);
+					let pending = Self::approvals(room_id)
+						.iter()
+						.filter_map(|id| Self::proposals(room_id, id))
+						.filter(|p| {
+							p.currency_id == T::GetNativeCurrencyId::get() &&
+								p.start_spend_time.map(Self::period_start) == Some(period_start)
+						})
+						.fold(proposal.value, |acc, p| acc.saturating_add(p.value));
+					ensure!(pending <= max_spend, Error::<T>::BudgetExceeded);
+				}
+			}
 
+			<Approvals<T>>::try_mutate(room_id, |h| h.try_push(proposal_id))
+				.map_err(|_| Error::<T>::TooManyApprovals)?;
 			<Proposals<T>>::mutate(room_id, proposal_id, |h| {
 				if let Some(p) = h {
-					p.start_spend_time = Some(Self::now() + T::SpendPeriod::get());
+					p.start_spend_time = Some(start_spend_time);
 				}
 			});
-
-			<Approvals<T>>::mutate(room_id, |h| h.push(proposal_id));
 			Ok(())
 		}
 
@@ -228,33 +618,160 @@ pub mod pallet {
 		#[pallet::weight(1500_000_000)]
 		pub fn spend_fund(origin: OriginFor<T>, room_id: RoomIndex) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let mut proposal_ids = <Approvals<T>>::get(room_id);
-			if proposal_ids.len() == 0 {
+			if <Approvals<T>>::get(room_id).len() == 0 {
 				return Err(Error::<T>::RoomHaveNoProposal)?
 			}
 
-			for proposal_id in proposal_ids.clone().iter() {
-				if let Some(proposal) = <Proposals<T>>::get(room_id, proposal_id) {
-					if proposal.start_spend_time.is_some() &&
-						proposal.start_spend_time.unwrap() <= Self::now() &&
-						T::ListenHandler::sub_room_free_amount(
-							room_id.into(),
-							proposal.value.saturated_into::<u128>(),
-						)
-						.is_ok()
-					{
-						T::NativeCurrency::deposit_creating(&proposal.beneficiary, proposal.value);
+			Self::pay_approvals(room_id, 0, u32::MAX);
 
-						T::NativeCurrency::unreserve(&proposal.proposer, proposal.bond);
-						proposal_ids.retain(|h| h != proposal_id);
-						<Proposals<T>>::remove(room_id, proposal_id);
-					}
-				}
+			Self::deposit_event(Event::<T>::SpendFund(who, room_id));
+			Ok(())
+		}
+
+		/// Pay a single approved proposal whose spend period has passed. Only its beneficiary or
+		/// its proposer can do this.
+		#[pallet::weight(1500_000_000)]
+		pub fn claim_spend(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let proposal = Self::proposals(room_id, proposal_id).ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(
+				who == proposal.beneficiary || who == proposal.proposer,
+				Error::<T>::NotBeneficiaryOrProposer
+			);
+			ensure!(Self::approvals(room_id).contains(&proposal_id), Error::<T>::NotApproved);
+			let start_spend_time = proposal.start_spend_time.ok_or(Error::<T>::NotApproved)?;
+			ensure!(start_spend_time <= Self::now(), Error::<T>::NotMatured);
+
+			Self::pay_from_pot(room_id, proposal_id, &proposal)?;
+			Self::release_bond(&proposal);
+			Self::remove_proposal(room_id, proposal_id);
+
+			Self::deposit_event(Event::<T>::Awarded(
+				room_id,
+				proposal_id,
+				proposal.currency_id,
+				proposal.value,
+				proposal.beneficiary,
+			));
+			Ok(())
+		}
+
+		/// Move funds from the caller into the pot of a room, adding them to the room's free
+		/// amount of that currency.
+		#[pallet::weight(1500_000_000)]
+		pub fn fund_room(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			currency_id: CurrencyId,
+			#[pallet::compact] value: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			T::MultiCurrency::transfer(currency_id, &who, &Self::room_account_id(room_id), value)?;
+			T::ListenHandler::add_room_currency_free_amount(
+				room_id.into(),
+				currency_id,
+				value.saturated_into::<u128>(),
+			)?;
+
+			Self::deposit_event(Event::<T>::Deposit(who, room_id, currency_id, value));
+			Ok(())
+		}
+
+		/// Stop the vesting of a scheduled spend, returning what the beneficiary can not spend
+		/// yet to the room's pot.
+		#[pallet::weight(1500_000_000)]
+		#[transactional]
+		pub fn cancel_grant(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			let grant = <Grants<T>>::take(room_id, proposal_id).ok_or(Error::<T>::NoGrant)?;
+			let returned = T::VestedTransfer::revoke_vested_transfer(
+				&grant.beneficiary,
+				&Self::room_account_id(room_id),
+				grant.start,
+				grant.period,
+				grant.period_count,
+				grant.per_period,
+			)?;
+			T::ListenHandler::add_room_currency_free_amount(
+				room_id.into(),
+				T::GetNativeCurrencyId::get(),
+				returned.saturated_into::<u128>(),
+			)?;
+
+			Self::deposit_event(Event::<T>::GrantCancelled(room_id, proposal_id, returned));
+			Ok(())
+		}
+
+		/// Drop the vesting grant of a scheduled spend once it is fully vested.
+		#[pallet::weight(1500_000_000)]
+		pub fn prune_grant(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let grant = Self::grants(room_id, proposal_id).ok_or(Error::<T>::NoGrant)?;
+			let end = grant
+				.start
+				.saturating_add(grant.period.saturating_mul(grant.period_count.into()));
+			ensure!(end <= Self::now(), Error::<T>::GrantNotVested);
+			<Grants<T>>::remove(room_id, proposal_id);
+
+			Self::deposit_event(Event::<T>::GrantPruned(room_id, proposal_id));
+			Ok(())
+		}
+
+		/// Set the most a room can pay out in its native currency per `SpendPeriod`, and the
+		/// part of its idle free amount that is burnt at the end of every `SpendPeriod`. Only the
+		/// room's own governance can do this.
+		#[pallet::weight(1500_000_000)]
+		pub fn set_room_budget(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			max_spend: Option<BalanceOf<T>>,
+			burn: Option<Permill>,
+		) -> DispatchResult {
+			Self::ensure_room_origin(origin, room_id)?;
+
+			if max_spend.is_none() && burn.is_none() {
+				<RoomBudgets<T>>::remove(room_id);
+			} else {
+				<RoomBudgets<T>>::insert(room_id, RoomBudget { max_spend, burn });
+			}
+			if max_spend.is_none() {
+				<PeriodSpending<T>>::remove(room_id);
 			}
 
-			<Approvals<T>>::insert(room_id, proposal_ids);
+			Self::deposit_event(Event::<T>::BudgetSet(room_id, max_spend, burn));
+			Ok(())
+		}
 
-			Self::deposit_event(Event::<T>::SpendFund(who, room_id));
+		/// Choose what happens to the approved proposals of a room when it is dissolved. `None`
+		/// falls back to `DissolutionPolicy`.
+		#[pallet::weight(1500_000_000)]
+		pub fn set_dissolution_policy(
+			origin: OriginFor<T>,
+			room_id: RoomIndex,
+			policy: Option<DissolutionPolicy>,
+		) -> DispatchResult {
+			Self::ensure_room_origin(origin, room_id)?;
+
+			match policy {
+				Some(policy) => <RoomDissolutionPolicies<T>>::insert(room_id, policy),
+				None => <RoomDissolutionPolicies<T>>::remove(room_id),
+			}
+
+			Self::deposit_event(Event::<T>::DissolutionPolicySet(room_id, policy));
 			Ok(())
 		}
 	}
@@ -267,13 +784,762 @@ pub mod pallet {
 		pub fn now() -> T::BlockNumber {
 			<frame_system::Pallet<T>>::block_number()
 		}
+
+		/// The account holding the funds of a room.
+		pub fn room_account_id(room_id: RoomIndex) -> T::AccountId {
+			T::PalletId::get().into_sub_account(room_id)
+		}
+
+		/// Pay `value` of the native currency from the pot of a room to `dest`, taking it out of
+		/// the room's free amount and counting it against the room's budget. For the pallets
+		/// that spend from the pot without a spend proposal.
+		#[transactional]
+		pub fn spend_from_pot(
+			room_id: RoomIndex,
+			dest: &T::AccountId,
+			value: BalanceOf<T>,
+		) -> DispatchResult {
+			Self::note_spending(room_id, value)?;
+			T::ListenHandler::sub_room_free_amount(room_id.into(), value.saturated_into::<u128>())
+				.map_err(|_| Error::<T>::InsufficientRoomFunds)?;
+			T::NativeCurrency::transfer(
+				&Self::room_account_id(room_id),
+				dest,
+				value,
+				ExistenceRequirement::AllowDeath,
+			)
+			.map_err(|_| Error::<T>::InsufficientRoomFunds)?;
+			Ok(())
+		}
+
+		fn ensure_room_origin(origin: OriginFor<T>, room_id: RoomIndex) -> DispatchResult {
+			let governed = T::RoomOrigin::ensure_origin(origin)?;
+			ensure!(governed == room_id, DispatchError::BadOrigin);
+			Ok(())
+		}
+
+		// Looks at the approvals from `cursor` on until the per-block budget is used up.
+		fn continue_payouts(cursor: Option<(RoomIndex, u32)>) -> Weight {
+			let budget = T::MaxPayoutsPerBlock::get();
+			let (first, rest) = match cursor {
+				Some((room_id, skip)) => (
+					Some((room_id, skip)),
+					<Approvals<T>>::iter_keys_from(<Approvals<T>>::hashed_key_for(room_id)),
+				),
+				None => (None, <Approvals<T>>::iter_keys()),
+			};
+
+			let mut processed = 0u32;
+			let mut rooms_read = 0u64;
+			let mut stopped_at = None;
+			for (room_id, skip) in first.into_iter().chain(rest.map(|room_id| (room_id, 0))) {
+				if processed >= budget {
+					stopped_at = Some((room_id, skip));
+					break
+				}
+				rooms_read += 1;
+				let (count, next) = Self::pay_approvals(room_id, skip, budget - processed);
+				processed = processed.saturating_add(count);
+				if let Some(next) = next {
+					stopped_at = Some((room_id, next));
+					break
+				}
+			}
+
+			match stopped_at {
+				Some(cursor) => <PayoutRound<T>>::put(Some(cursor)),
+				None => <PayoutRound<T>>::kill(),
+			}
+			T::WeightInfo::on_initialize_proposals(processed)
+				.saturating_add(T::DbWeight::get().reads_writes(rooms_read * 2, rooms_read * 2 + 1))
+		}
+
+		// Pays the matured approvals of a room out of its pot, skipping the first `skip` that
+		// were already looked at this round and looking at no more than `limit`. Returns how
+		// many approvals were looked at and, if that was not all of them, how many to skip when
+		// carrying on. `Spending` is emitted when the room is started on, `Rollover` when it is
+		// done, for every currency its approvals are paid in.
+		fn pay_approvals(room_id: RoomIndex, skip: u32, limit: u32) -> (u32, Option<u32>) {
+			let now = Self::now();
+			let proposal_ids = <Approvals<T>>::get(room_id);
+			let mut currencies: Vec<CurrencyId> = Vec::new();
+			for proposal in proposal_ids.iter().filter_map(|id| Self::proposals(room_id, id)) {
+				if !currencies.contains(&proposal.currency_id) {
+					currencies.push(proposal.currency_id);
+				}
+			}
+			if skip == 0 {
+				for currency_id in currencies.iter() {
+					let budget = Self::room_free_amount(room_id, *currency_id);
+					Self::deposit_event(Event::<T>::Spending(room_id, *currency_id, budget));
+				}
+			}
+
+			let mut processed = 0u32;
+			let mut kept = skip;
+			let mut next = None;
+			for proposal_id in proposal_ids.iter().skip(skip as usize) {
+				if processed >= limit {
+					next = Some(kept);
+					break
+				}
+				processed += 1;
+				match <Proposals<T>>::get(room_id, proposal_id) {
+					Some(proposal)
+						if proposal.start_spend_time.map_or(false, |start| start <= now) &&
+							Self::pay_from_pot(room_id, *proposal_id, &proposal).is_ok() =>
+					{
+						Self::release_bond(&proposal);
+						Self::remove_proposal(room_id, *proposal_id);
+						Self::deposit_event(Event::<T>::Awarded(
+							room_id,
+							*proposal_id,
+							proposal.currency_id,
+							proposal.value,
+							proposal.beneficiary,
+						));
+					},
+					_ => kept += 1,
+				}
+			}
+
+			if next.is_none() {
+				for currency_id in currencies {
+					let rollover = Self::room_free_amount(room_id, currency_id);
+					Self::deposit_event(Event::<T>::Rollover(room_id, currency_id, rollover));
+				}
+			}
+			(processed, next)
+		}
+
+		fn room_free_amount(room_id: RoomIndex, currency_id: CurrencyId) -> BalanceOf<T> {
+			T::ListenHandler::get_room_currency_free_amount(room_id.into(), currency_id)
+				.saturated_into()
+		}
+
+		// Pays a spend out of the room's pot, keeping the room's free amount in step. A spend
+		// with a payout schedule is vested to the beneficiary starting from its offset.
+		#[transactional]
+		fn pay_from_pot(
+			room_id: RoomIndex,
+			proposal_id: ProposalIndex,
+			proposal: &RoomTreasuryProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			if proposal.currency_id == T::GetNativeCurrencyId::get() {
+				Self::note_spending(room_id, proposal.value)?;
+			}
+			T::ListenHandler::sub_room_currency_free_amount(
+				room_id.into(),
+				proposal.currency_id,
+				proposal.value.saturated_into::<u128>(),
+			)
+			.map_err(|_| Error::<T>::InsufficientRoomFunds)?;
+
+			let schedule = match <SpendSchedules<T>>::take(room_id, proposal_id) {
+				Some(schedule) => schedule,
+				None => {
+					T::MultiCurrency::transfer(
+						proposal.currency_id,
+						&Self::room_account_id(room_id),
+						&proposal.beneficiary,
+						proposal.value,
+					)
+					.map_err(|_| Error::<T>::InsufficientRoomFunds)?;
+					return Ok(())
+				},
+			};
+			let grant = VestingGrant {
+				beneficiary: proposal.beneficiary.clone(),
+				start: Self::now().saturating_add(schedule.start_offset),
+				period: schedule.period,
+				period_count: schedule.period_count,
+				per_period: proposal.value / BalanceOf::<T>::from(schedule.period_count),
+			};
+			T::VestedTransfer::vested_transfer(
+				&Self::room_account_id(room_id),
+				&grant.beneficiary,
+				grant.start,
+				grant.period,
+				grant.period_count,
+				grant.per_period,
+			)?;
+			<Grants<T>>::insert(room_id, proposal_id, grant);
+			Ok(())
+		}
+
+		// The start of the `SpendPeriod` that `n` is in.
+		fn period_start(n: T::BlockNumber) -> T::BlockNumber {
+			let period = T::SpendPeriod::get();
+			if period.is_zero() {
+				n
+			} else {
+				n - n % period
+			}
+		}
+
+		fn max_spend(room_id: RoomIndex) -> Option<BalanceOf<T>> {
+			Self::room_budgets(room_id).and_then(|budget| budget.max_spend)
+		}
+
+		// Adds `value` to what the room has paid out this period, failing if that goes over its
+		// maximum spend.
+		fn note_spending(room_id: RoomIndex, value: BalanceOf<T>) -> DispatchResult {
+			let max_spend = match Self::max_spend(room_id) {
+				Some(max_spend) => max_spend,
+				None => return Ok(()),
+			};
+			let period_start = Self::period_start(Self::now());
+			<PeriodSpending<T>>::try_mutate(room_id, |(start, spent)| {
+				if *start != period_start {
+					*start = period_start;
+					*spent = Zero::zero();
+				}
+				let total = spent.saturating_add(value);
+				ensure!(total <= max_spend, Error::<T>::BudgetExceeded);
+				*spent = total;
+				Ok(())
+			})
+		}
+
+		// Looks at the rooms with a budget after `cursor`, burning the idle funds of up to
+		// `MaxPayoutsPerBlock` of them.
+		fn continue_burns(cursor: Option<RoomIndex>) -> Weight {
+			let budget = T::MaxPayoutsPerBlock::get() as usize;
+			let rooms: Vec<(RoomIndex, RoomBudget<BalanceOf<T>>)> = match cursor {
+				Some(room_id) =>
+					<RoomBudgets<T>>::iter_from(<RoomBudgets<T>>::hashed_key_for(room_id))
+						.take(budget)
+						.collect(),
+				None => <RoomBudgets<T>>::iter().take(budget).collect(),
+			};
+
+			let read = rooms.len() as u64;
+			let mut burnt = 0u64;
+			for (room_id, room_budget) in rooms.iter() {
+				if let Some(burn) = room_budget.burn {
+					if Self::burn_idle_funds(*room_id, burn).is_ok() {
+						burnt += 1;
+					}
+				}
+			}
+
+			match rooms.last() {
+				Some((room_id, _)) if rooms.len() == budget => <BurnRound<T>>::put(Some(*room_id)),
+				_ => <BurnRound<T>>::kill(),
+			}
+			T::DbWeight::get().reads_writes(read + burnt * 2, burnt * 2 + 1)
+		}
+
+		// Burns `burn` of the room's free amount that its approvals do not need out of its pot.
+		#[transactional]
+		fn burn_idle_funds(room_id: RoomIndex, burn: Permill) -> DispatchResult {
+			// the funds the approvals waiting to be paid need are not idle
+			let free = T::ListenHandler::get_room_free_amount(room_id.into())
+				.saturating_sub(Self::pending_spends(room_id));
+			let amount: BalanceOf<T> = (burn * free).saturated_into();
+			if amount.is_zero() {
+				return Ok(())
+			}
+
+			T::ListenHandler::sub_room_free_amount(
+				room_id.into(),
+				amount.saturated_into::<u128>(),
+			)?;
+			let imbalance = T::NativeCurrency::withdraw(
+				&Self::room_account_id(room_id),
+				amount,
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			drop(imbalance);
+
+			Self::deposit_event(Event::<T>::Burnt(room_id, amount));
+			Ok(())
+		}
+
+		// Looks at the proposals after the last one the sweep looked at while the weight allows,
+		// expiring unapproved ones older than `ProposalLifetime` and lapsing approved ones that
+		// matured more than `ClaimWindow` ago. Starts over once all were looked at.
+		fn sweep_expired(remaining_weight: Weight) -> Weight {
+			let db = T::DbWeight::get();
+			let per_proposal = db.reads_writes(3, 5);
+			let mut used = db.reads_writes(1, 1);
+			if used.saturating_add(per_proposal) > remaining_weight {
+				return 0
+			}
+
+			let now = Self::now();
+			let mut proposals = match Self::expiry_cursor() {
+				Some(key) => <Proposals<T>>::iter_from(key),
+				None => <Proposals<T>>::iter(),
+			};
+			let mut last = None;
+			let mut finished = false;
+			while used.saturating_add(per_proposal) <= remaining_weight {
+				let (room_id, proposal_id, proposal) = match proposals.next() {
+					Some(item) => item,
+					None => {
+						finished = true;
+						break
+					},
+				};
+				used = used.saturating_add(per_proposal);
+				last = Some(<Proposals<T>>::hashed_key_for(room_id, proposal_id));
+				// their bonds are returned in full by the dissolution
+				if <DissolvingRooms<T>>::contains_key(room_id) {
+					continue
+				}
+
+				match proposal.start_spend_time {
+					None => match Self::proposed_at(room_id, proposal_id) {
+						// proposals made before their age was kept start aging now
+						None => <ProposedAt<T>>::insert(room_id, proposal_id, now),
+						Some(at) if now >= at.saturating_add(T::ProposalLifetime::get()) =>
+							Self::expire_proposal(room_id, proposal_id, &proposal),
+						Some(_) => {},
+					},
+					Some(matured) if now >= matured.saturating_add(T::ClaimWindow::get()) =>
+						Self::lapse_spend(room_id, proposal_id, &proposal),
+					Some(_) => {},
+				}
+			}
+
+			if finished {
+				<ExpiryCursor<T>>::kill();
+			} else if let Some(key) = last {
+				<ExpiryCursor<T>>::put(key);
+			}
+			used
+		}
+
+		fn expire_proposal(
+			room_id: RoomIndex,
+			proposal_id: ProposalIndex,
+			proposal: &RoomTreasuryProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		) {
+			let fee = T::ExpiryFee::get() * proposal.bond;
+			Self::slash_bond(proposal, fee);
+			T::MultiCurrency::unreserve(
+				proposal.bond_currency_id,
+				&proposal.proposer,
+				proposal.bond.saturating_sub(fee),
+			);
+			Self::remove_proposal(room_id, proposal_id);
+			Self::deposit_event(Event::<T>::ProposalExpired(room_id, proposal_id, fee));
+		}
+
+		// The spend was never paid, so its funds are still in the room's pot.
+		fn lapse_spend(
+			room_id: RoomIndex,
+			proposal_id: ProposalIndex,
+			proposal: &RoomTreasuryProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		) {
+			Self::release_bond(proposal);
+			Self::remove_proposal(room_id, proposal_id);
+			Self::deposit_event(Event::<T>::SpendLapsed(room_id, proposal_id));
+		}
+
+		// Forgets a proposal, taking it out of the room's approvals if it was approved.
+		fn remove_proposal(room_id: RoomIndex, proposal_id: ProposalIndex) {
+			<Proposals<T>>::remove(room_id, proposal_id);
+			<ProposedAt<T>>::remove(room_id, proposal_id);
+			<SpendSchedules<T>>::remove(room_id, proposal_id);
+			let mut proposal_ids = Self::approvals(room_id);
+			if proposal_ids.contains(&proposal_id) {
+				proposal_ids.retain(|h| h != &proposal_id);
+				if proposal_ids.is_empty() {
+					<Approvals<T>>::remove(room_id);
+				} else {
+					<Approvals<T>>::insert(room_id, proposal_ids);
+				}
+			}
+		}
+
+		// Moves what is left of `currency_id` in the pot of a dissolved room to
+		// `DissolvedPotBeneficiary`, taking it out of the room's free amount.
+		fn sweep_pot(room_id: RoomIndex, currency_id: CurrencyId) {
+			let pot = Self::room_account_id(room_id);
+			let amount = T::MultiCurrency::free_balance(currency_id, &pot);
+			if amount.is_zero() ||
+				T::MultiCurrency::transfer(
+					currency_id,
+					&pot,
+					&T::DissolvedPotBeneficiary::get(),
+					amount,
+				)
+				.is_err()
+			{
+				return
+			}
+			let free = T::ListenHandler::get_room_currency_free_amount(room_id.into(), currency_id);
+			let _ = T::ListenHandler::sub_room_currency_free_amount(
+				room_id.into(),
+				currency_id,
+				free.min(amount.saturated_into::<u128>()),
+			);
+			Self::deposit_event(Event::<T>::PotSwept(room_id, currency_id, amount));
+		}
+
+		// Removes at most `limit` items of the storage of a dissolved room, and the room's
+		// remaining entries once every map is cleared.
+		fn continue_dissolution(room_id: RoomIndex, mut limit: u32) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let (mut stage, mut cursor) = match Self::dissolving_rooms(room_id) {
+				Some(progress) => progress,
+				None => return weight,
+			};
+
+			while limit > 0 {
+				let (loops, done) = match stage {
+					0 => {
+						let loops = Self::release_room_bonds(room_id, limit);
+						(loops, loops < limit)
+					},
+					1 => {
+						let result = <Grants<T>>::clear_prefix(room_id, limit, cursor.as_deref());
+						cursor = result.maybe_cursor;
+						(result.loops, cursor.is_none())
+					},
+					2 => {
+						let result =
+							<RoomCurrencyFunds<T>>::clear_prefix(room_id, limit, cursor.as_deref());
+						cursor = result.maybe_cursor;
+						(result.loops, cursor.is_none())
+					},
+					_ => {
+						<DissolvingRooms<T>>::remove(room_id);
+						<ProposalCount<T>>::remove(room_id);
+						Self::deposit_event(Event::<T>::RoomDissolved(room_id));
+						return weight.saturating_add(T::DbWeight::get().writes(2))
+					},
+				};
+				limit = limit.saturating_sub(loops);
+				weight = weight.saturating_add(
+					T::DbWeight::get().reads_writes(loops as u64 + 1, 3 * loops as u64),
+				);
+				if done {
+					stage += 1;
+				}
+			}
+
+			<DissolvingRooms<T>>::insert(room_id, (stage, cursor));
+			weight
+		}
+
+		// Returns the bonds of up to `limit` proposals of a dissolved room, removing them as they
+		// are read. Returns how many there were.
+		fn release_room_bonds(room_id: RoomIndex, limit: u32) -> u32 {
+			let mut loops = 0u32;
+			for (proposal_id, proposal) in
+				<Proposals<T>>::drain_prefix(room_id).take(limit as usize)
+			{
+				loops += 1;
+				Self::release_bond(&proposal);
+				<ProposedAt<T>>::remove(room_id, proposal_id);
+				<SpendSchedules<T>>::remove(room_id, proposal_id);
+				Self::deposit_event(Event::<T>::BondReleased(room_id, proposal_id, proposal.bond));
+			}
+			loops
+		}
+
+		fn slash_bond(
+			proposal: &RoomTreasuryProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+			value: BalanceOf<T>,
+		) {
+			if proposal.bond_currency_id == T::GetNativeCurrencyId::get() {
+				let imbalance = T::NativeCurrency::slash_reserved(&proposal.proposer, value).0;
+				T::OnSlash::on_unbalanced(imbalance);
+			} else {
+				T::MultiCurrency::slash_reserved(
+					proposal.bond_currency_id,
+					&proposal.proposer,
+					value,
+				);
+			}
+		}
+
+		fn release_bond(
+			proposal: &RoomTreasuryProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		) {
+			T::MultiCurrency::unreserve(
+				proposal.bond_currency_id,
+				&proposal.proposer,
+				proposal.bond,
+			);
+		}
+	}
+
+	impl<T: Config> PendingSpends<RoomIndex, u128> for Pallet<T> {
+		/// The value of the approved native-currency proposals of the room that have not been
+		/// paid yet.
+		fn pending_spends(room_id: RoomIndex) -> u128 {
+			Self::approvals(room_id)
+				.iter()
+				.filter_map(|proposal_id| Self::proposals(room_id, proposal_id))
+				.filter(|proposal| proposal.currency_id == T::GetNativeCurrencyId::get())
+				.fold(0u128, |pending, proposal| {
+					pending.saturating_add(proposal.value.saturated_into::<u128>())
+				})
+		}
 	}
 
 	impl<T: Config> RoomTreasuryHandler<RoomIndex> for Pallet<T> {
-		fn remove_room_treasury_info(room_id: RoomIndex) {
-			<Proposals<T>>::remove_prefix(room_id, None);
-			// todo Funding bills that have been agreed to pass should not be swept away
-			<Approvals<T>>::remove(room_id);
+		/// Pay or drop the approved proposals of the room according to its dissolution policy,
+		/// then sweep what is left in its pot to `DissolvedPotBeneficiary`, in the native
+		/// currency, the currencies of those proposals and the ones tracked by `RoomFunds`.
+		/// Approved proposals the room's funds can not cover are dropped, and the room's budget no
+		/// longer applies.
+		///
+		/// The bonds of the other proposals are returned and the room's storage removed up to
+		/// `DissolutionLimit` items right away and as many in every following block, until
+		/// `RoomDissolved` is emitted. The vesting grants of the room are left to run out; they
+		/// can no longer be cancelled.
+		fn remove_room_treasury_info(room_id: RoomIndex) -> Weight {
+			let policy = <RoomDissolutionPolicies<T>>::take(room_id)
+				.unwrap_or_else(T::DissolutionPolicy::get);
+			let approvals = <Approvals<T>>::take(room_id);
+			<RoomBudgets<T>>::remove(room_id);
+			<PeriodSpending<T>>::remove(room_id);
+			let mut weight = T::DbWeight::get()
+				.reads_writes(2 + 4 * approvals.len() as u64, 4 + 4 * approvals.len() as u64);
+
+			let mut currencies = vec![T::GetNativeCurrencyId::get()];
+			for proposal_id in approvals {
+				let proposal = match Self::proposals(room_id, proposal_id) {
+					Some(proposal) => proposal,
+					None => continue,
+				};
+				if !currencies.contains(&proposal.currency_id) {
+					currencies.push(proposal.currency_id);
+				}
+				Self::release_bond(&proposal);
+				let paid = policy == DissolutionPolicy::PayApproved &&
+					Self::pay_from_pot(room_id, proposal_id, &proposal).is_ok();
+				Self::remove_proposal(room_id, proposal_id);
+				if paid {
+					Self::deposit_event(Event::<T>::Awarded(
+						room_id,
+						proposal_id,
+						proposal.currency_id,
+						proposal.value,
+						proposal.beneficiary,
+					));
+				} else {
+					Self::deposit_event(Event::<T>::SpendRefunded(
+						room_id,
+						proposal_id,
+						proposal.bond,
+					));
+				}
+			}
+			for currency_id in <RoomCurrencyFunds<T>>::iter_key_prefix(room_id) {
+				if !currencies.contains(&currency_id) {
+					currencies.push(currency_id);
+				}
+			}
+			for currency_id in currencies {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 3));
+				Self::sweep_pot(room_id, currency_id);
+			}
+
+			<DissolvingRooms<T>>::insert(room_id, (0, None::<Vec<u8>>));
+			weight.saturating_add(Self::continue_dissolution(room_id, T::DissolutionLimit::get()))
+		}
+	}
+}
+
+/// A `MultiCurrencyListenHandler` for the rooms of `H`, which only keeps their free amounts in
+/// the native currency. Their free amounts in other currencies are kept by the treasury, in
+/// `RoomCurrencyFunds`.
+pub struct RoomFunds<T, H>(sp_std::marker::PhantomData<(T, H)>);
+
+impl<T: Config, H: ListenHandler<RoomIndex, T::AccountId, DispatchError, u128>>
+	ListenHandler<RoomIndex, T::AccountId, DispatchError, u128> for RoomFunds<T, H>
+{
+	fn get_room_council(room_id: RoomIndex) -> Result<Vec<T::AccountId>, DispatchError> {
+		H::get_room_council(room_id)
+	}
+
+	fn get_prime(room_id: RoomIndex) -> Result<Option<T::AccountId>, DispatchError> {
+		H::get_prime(room_id)
+	}
+
+	fn get_root(room_id: RoomIndex) -> Result<T::AccountId, DispatchError> {
+		H::get_root(room_id)
+	}
+
+	fn get_room_free_amount(room_id: RoomIndex) -> u128 {
+		H::get_room_free_amount(room_id)
+	}
+
+	fn sub_room_free_amount(room_id: RoomIndex, amount: u128) -> Result<(), DispatchError> {
+		H::sub_room_free_amount(room_id, amount)
+	}
+
+	fn add_room_free_amount(room_id: RoomIndex, amount: u128) -> Result<(), DispatchError> {
+		H::add_room_free_amount(room_id, amount)
+	}
+
+	fn exit_room_council(
+		room_id: RoomIndex,
+		who: T::AccountId,
+		payout: u128,
+	) -> Result<(), DispatchError> {
+		H::exit_room_council(room_id, who, payout)
+	}
+
+	fn is_in_room(room_id: RoomIndex, who: T::AccountId) -> Result<bool, DispatchError> {
+		H::is_in_room(room_id, who)
+	}
+
+	fn set_room_council(
+		room_id: RoomIndex,
+		council: Vec<T::AccountId>,
+	) -> Result<(), DispatchError> {
+		H::set_room_council(room_id, council)
+	}
+}
+
+impl<T: Config, H: ListenHandler<RoomIndex, T::AccountId, DispatchError, u128>>
+	MultiCurrencyListenHandler<RoomIndex, T::AccountId, CurrencyId, DispatchError, u128>
+	for RoomFunds<T, H>
+{
+	fn get_room_currency_free_amount(room_id: RoomIndex, currency_id: CurrencyId) -> u128 {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			return H::get_room_free_amount(room_id)
 		}
+		<RoomCurrencyFunds<T>>::get(room_id, currency_id)
+	}
+
+	fn add_room_currency_free_amount(
+		room_id: RoomIndex,
+		currency_id: CurrencyId,
+		amount: u128,
+	) -> Result<(), DispatchError> {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			return H::add_room_free_amount(room_id, amount)
+		}
+		<RoomCurrencyFunds<T>>::mutate(room_id, currency_id, |free| {
+			*free = free.saturating_add(amount)
+		});
+		Ok(())
+	}
+
+	fn sub_room_currency_free_amount(
+		room_id: RoomIndex,
+		currency_id: CurrencyId,
+		amount: u128,
+	) -> Result<(), DispatchError> {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			return H::sub_room_free_amount(room_id, amount)
+		}
+		let free = <RoomCurrencyFunds<T>>::get(room_id, currency_id)
+			.checked_sub(amount)
+			.ok_or(Error::<T>::InsufficientRoomFunds)?;
+		if free == 0 {
+			<RoomCurrencyFunds<T>>::remove(room_id, currency_id);
+		} else {
+			<RoomCurrencyFunds<T>>::insert(room_id, currency_id, free);
+		}
+		Ok(())
+	}
+}
+
+/// A `MultiCurrency` for runtimes whose rooms only hold the native currency `C`: every currency
+/// id is taken to be the native one.
+pub struct NativeCurrencyOnly<C>(sp_std::marker::PhantomData<C>);
+
+impl<AccountId, C: ReservableCurrency<AccountId>> MultiCurrency<AccountId>
+	for NativeCurrencyOnly<C>
+{
+	type CurrencyId = CurrencyId;
+	type Balance = C::Balance;
+
+	fn minimum_balance(_currency_id: CurrencyId) -> C::Balance {
+		C::minimum_balance()
+	}
+
+	fn total_issuance(_currency_id: CurrencyId) -> C::Balance {
+		C::total_issuance()
+	}
+
+	fn total_balance(_currency_id: CurrencyId, who: &AccountId) -> C::Balance {
+		C::total_balance(who)
+	}
+
+	fn free_balance(_currency_id: CurrencyId, who: &AccountId) -> C::Balance {
+		C::free_balance(who)
+	}
+
+	fn ensure_can_withdraw(
+		_currency_id: CurrencyId,
+		who: &AccountId,
+		amount: C::Balance,
+	) -> DispatchResult {
+		let new_balance = C::free_balance(who)
+			.checked_sub(&amount)
+			.ok_or(DispatchError::Other("balance too low"))?;
+		C::ensure_can_withdraw(who, amount, WithdrawReasons::all(), new_balance)
+	}
+
+	fn transfer(
+		_currency_id: CurrencyId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: C::Balance,
+	) -> DispatchResult {
+		C::transfer(from, to, amount, ExistenceRequirement::AllowDeath)
+	}
+
+	fn deposit(_currency_id: CurrencyId, who: &AccountId, amount: C::Balance) -> DispatchResult {
+		C::deposit_creating(who, amount);
+		Ok(())
+	}
+
+	fn withdraw(_currency_id: CurrencyId, who: &AccountId, amount: C::Balance) -> DispatchResult {
+		C::withdraw(who, amount, WithdrawReasons::all(), ExistenceRequirement::AllowDeath)
+			.map(|_| ())
+	}
+
+	fn can_slash(_currency_id: CurrencyId, who: &AccountId, amount: C::Balance) -> bool {
+		C::can_slash(who, amount)
+	}
+
+	fn slash(_currency_id: CurrencyId, who: &AccountId, amount: C::Balance) -> C::Balance {
+		C::slash(who, amount).1
+	}
+}
+
+impl<AccountId, C: ReservableCurrency<AccountId>> MultiReservableCurrency<AccountId>
+	for NativeCurrencyOnly<C>
+{
+	fn can_reserve(_currency_id: CurrencyId, who: &AccountId, amount: C::Balance) -> bool {
+		C::can_reserve(who, amount)
+	}
+
+	fn slash_reserved(_currency_id: CurrencyId, who: &AccountId, amount: C::Balance) -> C::Balance {
+		C::slash_reserved(who, amount).1
+	}
+
+	fn reserved_balance(_currency_id: CurrencyId, who: &AccountId) -> C::Balance {
+		C::reserved_balance(who)
+	}
+
+	fn reserve(_currency_id: CurrencyId, who: &AccountId, amount: C::Balance) -> DispatchResult {
+		C::reserve(who, amount)
+	}
+
+	fn unreserve(_currency_id: CurrencyId, who: &AccountId, amount: C::Balance) -> C::Balance {
+		C::unreserve(who, amount)
+	}
+
+	fn repatriate_reserved(
+		_currency_id: CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		amount: C::Balance,
+		status: BalanceStatus,
+	) -> Result<C::Balance, DispatchError> {
+		C::repatriate_reserved(slashed, beneficiary, amount, status)
 	}
 }
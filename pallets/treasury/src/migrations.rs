@@ -0,0 +1,72 @@
+// Copyright 2021 LISTEN Developer.
+// This file is part of LISTEN.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the treasury pallet.
+
+use super::*;
+use frame_support::{
+	traits::{GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+
+/// Run every migration the on-chain storage version has not seen yet.
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+	let mut weight = T::DbWeight::get().reads(1);
+
+	if on_chain_version < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+	}
+
+	weight
+}
+
+/// `RoomTreasuryProposal` before storage version 1, paid and bonded in the native currency.
+#[derive(Encode, Decode)]
+pub struct RoomTreasuryProposalV0<AccountId, Balance, BlockNumber> {
+	proposer: AccountId,
+	value: Balance,
+	beneficiary: AccountId,
+	bond: Balance,
+	start_spend_time: Option<BlockNumber>,
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// Record the native currency as the spend and bond currency of existing proposals.
+	pub fn migrate<T: Config>() -> Weight {
+		let native = T::GetNativeCurrencyId::get();
+		let mut translated = 0u64;
+		Proposals::<T>::translate::<
+			RoomTreasuryProposalV0<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+			_,
+		>(|_, _, old| {
+			translated += 1;
+			Some(RoomTreasuryProposal {
+				proposer: old.proposer,
+				value: old.value,
+				beneficiary: old.beneficiary,
+				bond: old.bond,
+				start_spend_time: old.start_spend_time,
+				currency_id: native,
+				bond_currency_id: native,
+			})
+		});
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(translated, translated + 1)
+	}
+}
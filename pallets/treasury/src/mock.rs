@@ -0,0 +1,498 @@
+//! Mocks for the treasury module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	assert_ok, construct_runtime, parameter_types,
+	traits::{BalanceStatus, Everything, OnIdle, OnInitialize},
+	weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSigned, RawOrigin};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError, DispatchResult};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, result};
+
+use crate as treasury;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u128;
+pub type Balance = u64;
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxVestingSchedules: u32 = 2;
+	pub const MinVestedTransfer: Balance = 4;
+}
+
+impl orml_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type MinVestedTransfer = MinVestedTransfer;
+	type VestedTransferOrigin = EnsureSigned<AccountId>;
+	type WeightInfo = ();
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type BlockNumberProvider = System;
+}
+
+thread_local! {
+	static TOKENS: RefCell<BTreeMap<(CurrencyId, AccountId), (Balance, Balance)>> =
+		RefCell::new(BTreeMap::new());
+	static FREE_AMOUNTS: RefCell<BTreeMap<RoomIndex, u128>> = RefCell::new(BTreeMap::new());
+}
+
+fn tokens(currency_id: CurrencyId, who: &AccountId) -> (Balance, Balance) {
+	TOKENS.with(|t| t.borrow().get(&(currency_id, *who)).cloned().unwrap_or_default())
+}
+
+fn set_tokens(currency_id: CurrencyId, who: &AccountId, free: Balance, reserved: Balance) {
+	TOKENS.with(|t| t.borrow_mut().insert((currency_id, *who), (free, reserved)));
+}
+
+/// The native currency is kept by `Balances`, every other one in memory.
+pub struct Tokens;
+impl MultiCurrency<AccountId> for Tokens {
+	type CurrencyId = CurrencyId;
+	type Balance = Balance;
+
+	fn minimum_balance(_currency_id: CurrencyId) -> Balance {
+		ExistentialDeposit::get()
+	}
+
+	fn total_issuance(currency_id: CurrencyId) -> Balance {
+		if currency_id == NATIVE {
+			return Balances::total_issuance()
+		}
+		TOKENS.with(|t| {
+			t.borrow()
+				.iter()
+				.filter(|((id, _), _)| *id == currency_id)
+				.map(|(_, (free, reserved))| free + reserved)
+				.sum()
+		})
+	}
+
+	fn total_balance(currency_id: CurrencyId, who: &AccountId) -> Balance {
+		if currency_id == NATIVE {
+			return Balances::total_balance(who)
+		}
+		let (free, reserved) = tokens(currency_id, who);
+		free + reserved
+	}
+
+	fn free_balance(currency_id: CurrencyId, who: &AccountId) -> Balance {
+		if currency_id == NATIVE {
+			return Balances::free_balance(who)
+		}
+		tokens(currency_id, who).0
+	}
+
+	fn ensure_can_withdraw(
+		currency_id: CurrencyId,
+		who: &AccountId,
+		amount: Balance,
+	) -> DispatchResult {
+		if currency_id == NATIVE {
+			return Balances::ensure_can_withdraw(
+				who,
+				amount,
+				WithdrawReasons::all(),
+				Balances::free_balance(who).saturating_sub(amount),
+			)
+		}
+		ensure!(tokens(currency_id, who).0 >= amount, DispatchError::Other("balance too low"));
+		Ok(())
+	}
+
+	fn transfer(
+		currency_id: CurrencyId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+	) -> DispatchResult {
+		if currency_id == NATIVE {
+			return <Balances as Currency<AccountId>>::transfer(
+				from,
+				to,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)
+		}
+		Self::withdraw(currency_id, from, amount)?;
+		Self::deposit(currency_id, to, amount)
+	}
+
+	fn deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		if currency_id == NATIVE {
+			Balances::deposit_creating(who, amount);
+			return Ok(())
+		}
+		let (free, reserved) = tokens(currency_id, who);
+		set_tokens(currency_id, who, free + amount, reserved);
+		Ok(())
+	}
+
+	fn withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		if currency_id == NATIVE {
+			return Balances::withdraw(
+				who,
+				amount,
+				WithdrawReasons::all(),
+				ExistenceRequirement::AllowDeath,
+			)
+			.map(|_| ())
+		}
+		Self::ensure_can_withdraw(currency_id, who, amount)?;
+		let (free, reserved) = tokens(currency_id, who);
+		set_tokens(currency_id, who, free - amount, reserved);
+		Ok(())
+	}
+
+	fn can_slash(currency_id: CurrencyId, who: &AccountId, value: Balance) -> bool {
+		Self::free_balance(currency_id, who) >= value
+	}
+
+	fn slash(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> Balance {
+		if currency_id == NATIVE {
+			return Balances::slash(who, amount).1
+		}
+		let (free, reserved) = tokens(currency_id, who);
+		let slashed = free.min(amount);
+		set_tokens(currency_id, who, free - slashed, reserved);
+		amount - slashed
+	}
+}
+
+impl MultiReservableCurrency<AccountId> for Tokens {
+	fn can_reserve(currency_id: CurrencyId, who: &AccountId, value: Balance) -> bool {
+		Self::free_balance(currency_id, who) >= value
+	}
+
+	fn slash_reserved(currency_id: CurrencyId, who: &AccountId, value: Balance) -> Balance {
+		if currency_id == NATIVE {
+			return Balances::slash_reserved(who, value).1
+		}
+		let (free, reserved) = tokens(currency_id, who);
+		let slashed = reserved.min(value);
+		set_tokens(currency_id, who, free, reserved - slashed);
+		value - slashed
+	}
+
+	fn reserved_balance(currency_id: CurrencyId, who: &AccountId) -> Balance {
+		if currency_id == NATIVE {
+			return Balances::reserved_balance(who)
+		}
+		tokens(currency_id, who).1
+	}
+
+	fn reserve(currency_id: CurrencyId, who: &AccountId, value: Balance) -> DispatchResult {
+		if currency_id == NATIVE {
+			return Balances::reserve(who, value)
+		}
+		Self::ensure_can_withdraw(currency_id, who, value)?;
+		let (free, reserved) = tokens(currency_id, who);
+		set_tokens(currency_id, who, free - value, reserved + value);
+		Ok(())
+	}
+
+	fn unreserve(currency_id: CurrencyId, who: &AccountId, value: Balance) -> Balance {
+		if currency_id == NATIVE {
+			return Balances::unreserve(who, value)
+		}
+		let (free, reserved) = tokens(currency_id, who);
+		let unreserved = reserved.min(value);
+		set_tokens(currency_id, who, free + unreserved, reserved - unreserved);
+		value - unreserved
+	}
+
+	fn repatriate_reserved(
+		currency_id: CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Balance,
+		status: BalanceStatus,
+	) -> result::Result<Balance, DispatchError> {
+		if currency_id == NATIVE {
+			return Balances::repatriate_reserved(slashed, beneficiary, value, status)
+		}
+		let (free, reserved) = tokens(currency_id, slashed);
+		let moved = reserved.min(value);
+		set_tokens(currency_id, slashed, free, reserved - moved);
+		let (free, reserved) = tokens(currency_id, beneficiary);
+		match status {
+			BalanceStatus::Free => set_tokens(currency_id, beneficiary, free + moved, reserved),
+			BalanceStatus::Reserved => set_tokens(currency_id, beneficiary, free, reserved + moved),
+		}
+		Ok(value - moved)
+	}
+}
+
+pub fn room_free_amount(room_id: RoomIndex, currency_id: CurrencyId) -> u128 {
+	<Runtime as Config>::ListenHandler::get_room_currency_free_amount(room_id, currency_id)
+}
+
+fn native_free_amount(room_id: RoomIndex) -> u128 {
+	FREE_AMOUNTS.with(|f| f.borrow().get(&room_id).cloned().unwrap_or_default())
+}
+
+pub struct ListenHandlerMock;
+impl ListenHandler<RoomIndex, AccountId, DispatchError, u128> for ListenHandlerMock {
+	fn get_room_council(_room_id: RoomIndex) -> result::Result<Vec<AccountId>, DispatchError> {
+		Ok(vec![])
+	}
+
+	fn get_prime(_room_id: RoomIndex) -> result::Result<Option<AccountId>, DispatchError> {
+		Ok(None)
+	}
+
+	fn get_root(_room_id: RoomIndex) -> result::Result<AccountId, DispatchError> {
+		Ok(ALICE)
+	}
+
+	fn get_room_free_amount(room_id: RoomIndex) -> u128 {
+		native_free_amount(room_id)
+	}
+
+	fn sub_room_free_amount(room_id: RoomIndex, amount: u128) -> result::Result<(), DispatchError> {
+		let free = native_free_amount(room_id)
+			.checked_sub(amount)
+			.ok_or(DispatchError::Other("room free amount too low"))?;
+		FREE_AMOUNTS.with(|f| f.borrow_mut().insert(room_id, free));
+		Ok(())
+	}
+
+	fn add_room_free_amount(room_id: RoomIndex, amount: u128) -> result::Result<(), DispatchError> {
+		let free = native_free_amount(room_id).saturating_add(amount);
+		FREE_AMOUNTS.with(|f| f.borrow_mut().insert(room_id, free));
+		Ok(())
+	}
+
+	fn exit_room_council(
+		_room_id: RoomIndex,
+		_who: AccountId,
+		_payout: u128,
+	) -> result::Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn is_in_room(_room_id: RoomIndex, _who: AccountId) -> result::Result<bool, DispatchError> {
+		Ok(true)
+	}
+
+	fn set_room_council(
+		_room_id: RoomIndex,
+		_council: Vec<AccountId>,
+	) -> result::Result<(), DispatchError> {
+		Ok(())
+	}
+}
+
+/// `COUNCIL + room_id` stands in for a motion of the council of the room.
+pub struct EnsureRoomCouncil;
+impl EnsureOrigin<Origin> for EnsureRoomCouncil {
+	type Success = RoomIndex;
+
+	fn try_origin(o: Origin) -> result::Result<Self::Success, Origin> {
+		Into::<result::Result<RawOrigin<AccountId>, Origin>>::into(o).and_then(|o| match o {
+			RawOrigin::Signed(who) if who >= COUNCIL => Ok((who - COUNCIL) as RoomIndex),
+			r => Err(Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::from(RawOrigin::Signed(COUNCIL))
+	}
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = NATIVE;
+	pub static BondInSpendCurrency: bool = false;
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 1;
+	pub const SpendPeriod: u64 = 5;
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxPayoutsPerBlock: u32 = 2;
+	pub const MaxApprovals: u32 = 4;
+	pub static Policy: DissolutionPolicy = DissolutionPolicy::PayApproved;
+	pub const ProposalLifetime: u64 = 20;
+	pub const ExpiryFee: Permill = Permill::from_percent(10);
+	pub const ClaimWindow: u64 = 20;
+	pub const DissolvedPotBeneficiary: AccountId = RESERVE;
+	pub const DissolutionLimit: u32 = 2;
+}
+
+impl Config for Runtime {
+	type NativeCurrency = Balances;
+	type MultiCurrency = Tokens;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type BondInSpendCurrency = BondInSpendCurrency;
+	type ListenHandler = RoomFunds<Runtime, ListenHandlerMock>;
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type RejectOrigin = EnsureRoot<AccountId>;
+	type Event = Event;
+	type OnSlash = ();
+	type WeightInfo = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type PalletId = TreasuryPalletId;
+	type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+	type MaxApprovals = MaxApprovals;
+	type DissolutionPolicy = Policy;
+	type RoomOrigin = EnsureRoomCouncil;
+	type DissolvedPotBeneficiary = DissolvedPotBeneficiary;
+	type DissolutionLimit = DissolutionLimit;
+	type ProposalLifetime = ProposalLifetime;
+	type ExpiryFee = ExpiryFee;
+	type ClaimWindow = ClaimWindow;
+	type VestedTransfer = Vesting;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Vesting: orml_vesting::{Pallet, Storage, Call, Event<T>, Config<T>},
+		Treasury: treasury::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const RESERVE: AccountId = 99;
+pub const COUNCIL: AccountId = 1000;
+
+pub const ROOM: RoomIndex = 1;
+pub const OTHER_ROOM: RoomIndex = 2;
+
+pub const NATIVE: CurrencyId = 0;
+pub const TOKEN: CurrencyId = 1;
+
+/// The origin of a motion of the council of `room_id`.
+pub fn council(room_id: RoomIndex) -> Origin {
+	Origin::signed(COUNCIL + room_id as AccountId)
+}
+
+/// Moves `value` of `currency_id` into the pot of `room_id` from `ALICE`.
+pub fn fund(room_id: RoomIndex, currency_id: CurrencyId, value: Balance) {
+	assert_ok!(Treasury::fund_room(Origin::signed(ALICE), room_id, currency_id, value));
+}
+
+/// Has `BOB` propose to pay `value` of `currency_id` to `CHARLIE`, and returns the proposal.
+pub fn propose(room_id: RoomIndex, currency_id: CurrencyId, value: Balance) -> ProposalIndex {
+	assert_ok!(Treasury::propose_spend(
+		Origin::signed(BOB),
+		room_id,
+		currency_id,
+		value,
+		CHARLIE,
+		None
+	));
+	Treasury::proposal_count(room_id) - 1
+}
+
+/// Proposes and approves a spend, returning the proposal.
+pub fn approve(room_id: RoomIndex, currency_id: CurrencyId, value: Balance) -> ProposalIndex {
+	let proposal_id = propose(room_id, currency_id, value);
+	assert_ok!(Treasury::approve_proposal(Origin::root(), room_id, proposal_id));
+	proposal_id
+}
+
+/// Runs the hooks of every block up to and including `n`.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Treasury::on_initialize(System::block_number());
+		Treasury::on_idle(System::block_number(), Weight::MAX);
+	}
+}
+
+pub fn treasury_events() -> Vec<crate::Event<Runtime>> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| match r.event {
+			Event::Treasury(inner) => Some(inner),
+			_ => None,
+		})
+		.collect()
+}
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 1000), (BOB, 100), (CHARLIE, 1)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		TOKENS.with(|t| {
+			*t.borrow_mut() =
+				BTreeMap::from([((TOKEN, ALICE), (1000, 0)), ((TOKEN, BOB), (100, 0))])
+		});
+		FREE_AMOUNTS.with(|f| f.borrow_mut().clear());
+		BondInSpendCurrency::set(false);
+		Policy::set(DissolutionPolicy::PayApproved);
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -1,367 +1,604 @@
-// This file is part of Substrate.
-
-// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd.
-// SPDX-License-Identifier: Apache-2.0
-
-// Licensed under the Apache License, Version 2.0 (the "License");
-// you may not use this file except in compliance with the License.
-// You may obtain a copy of the License at
-//
-// 	http://www.apache.org/licenses/LICENSE-2.0
-//
-// Unless required by applicable law or agreed to in writing, software
-// distributed under the License is distributed on an "AS IS" BASIS,
-// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
-// See the License for the specific language governing permissions and
-// limitations under the License.
-
-//! Treasury pallet tests.
+//! Unit tests for the treasury module.
 
 #![cfg(test)]
 
 use super::*;
-use crate as treasury;
-use frame_support::{assert_noop, assert_ok, parameter_types, traits::OnInitialize};
-use std::cell::RefCell;
-
-use sp_core::H256;
-use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
-	ModuleId,
-};
-
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
-type Block = frame_system::mocking::MockBlock<Test>;
-
-frame_support::construct_runtime!(
-	pub enum Test where
-		Block = Block,
-		NodeBlock = Block,
-		UncheckedExtrinsic = UncheckedExtrinsic,
-	{
-		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Treasury: treasury::{Pallet, Call, Storage, Config, Event<T>},
-	}
-);
-
-parameter_types! {
-	pub const BlockHashCount: u64 = 250;
-	pub BlockWeights: frame_system::limits::BlockWeights =
-		frame_system::limits::BlockWeights::simple_max(1024);
-}
-impl frame_system::Config for Test {
-	type BaseCallFilter = ();
-	type BlockWeights = ();
-	type BlockLength = ();
-	type DbWeight = ();
-	type Origin = Origin;
-	type Index = u64;
-	type BlockNumber = u64;
-	type Call = Call;
-	type Hash = H256;
-	type Hashing = BlakeTwo256;
-	type AccountId = u128; // u64 is not enough to hold bytes used to generate bounty account
-	type Lookup = IdentityLookup<Self::AccountId>;
-	type Header = Header;
-	type Event = Event;
-	type BlockHashCount = BlockHashCount;
-	type Version = ();
-	type PalletInfo = PalletInfo;
-	type AccountData = pallet_balances::AccountData<u64>;
-	type OnNewAccount = ();
-	type OnKilledAccount = ();
-	type SystemWeightInfo = ();
-	type SS58Prefix = ();
-	type OnSetCode = ();
-}
-parameter_types! {
-	pub const ExistentialDeposit: u64 = 1;
-}
-impl pallet_balances::Config for Test {
-	type MaxLocks = ();
-	type Balance = u64;
-	type Event = Event;
-	type DustRemoval = ();
-	type ExistentialDeposit = ExistentialDeposit;
-	type AccountStore = System;
-	type WeightInfo = ();
-}
-thread_local! {
-	static TEN_TO_FOURTEEN: RefCell<Vec<u128>> = RefCell::new(vec![10,11,12,13,14]);
-}
-parameter_types! {
-	pub const ProposalBond: Permill = Permill::from_percent(5);
-	pub const ProposalBondMinimum: u64 = 1;
-	pub const SpendPeriod: u64 = 2;
-	pub const Burn: Permill = Permill::from_percent(50);
-	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
-	pub const BountyUpdatePeriod: u32 = 20;
-	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
-	pub const BountyValueMinimum: u64 = 1;
-}
-impl Config for Test {
-	type ModuleId = TreasuryModuleId;
-	type Currency = pallet_balances::Pallet<Test>;
-	type ApproveOrigin = frame_system::EnsureRoot<u128>;
-	type RejectOrigin = frame_system::EnsureRoot<u128>;
-	type Event = Event;
-	type OnSlash = ();
-	type ProposalBond = ProposalBond;
-	type ProposalBondMinimum = ProposalBondMinimum;
-	type SpendPeriod = SpendPeriod;
-	type Burn = Burn;
-	type BurnDestination = (); // Just gets burned.
-	type WeightInfo = ();
-	type SpendFunds = ();
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+fn pot(room_id: RoomIndex) -> Balance {
+	Balances::free_balance(Treasury::room_account_id(room_id))
 }
 
-pub fn new_test_ext() -> sp_io::TestExternalities {
-	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
-	pallet_balances::GenesisConfig::<Test> {
-		// Total issuance will be 200 with treasury account initialized at ED.
-		balances: vec![(0, 100), (1, 98), (2, 1)],
-	}
-	.assimilate_storage(&mut t)
-	.unwrap();
-	treasury::GenesisConfig::default()
-		.assimilate_storage::<Test, _>(&mut t)
-		.unwrap();
-	t.into()
+#[test]
+fn fund_room_moves_funds_into_the_room_pot() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ne!(Treasury::room_account_id(ROOM), Treasury::room_account_id(OTHER_ROOM));
+
+		fund(ROOM, NATIVE, 100);
+		System::assert_last_event(Event::Treasury(crate::Event::Deposit(ALICE, ROOM, NATIVE, 100)));
+		assert_eq!(Balances::free_balance(ALICE), 900);
+		assert_eq!(pot(ROOM), 100);
+		assert_eq!(pot(OTHER_ROOM), 0);
+		assert_eq!(room_free_amount(ROOM, NATIVE), 100);
+
+		assert_noop!(
+			Treasury::fund_room(Origin::signed(CHARLIE), ROOM, NATIVE, 10),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+	});
 }
 
 #[test]
-fn genesis_config_works() {
-	new_test_ext().execute_with(|| {
-		assert_eq!(Treasury::pot(), 0);
-		assert_eq!(Treasury::proposal_count(), 0);
+fn spends_are_paid_from_the_room_pot() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, NATIVE, 100);
+		let proposal_id = approve(ROOM, NATIVE, 40);
+		assert_eq!(Balances::reserved_balance(BOB), 2);
+
+		// the first payout round comes before the spend matures
+		run_to_block(SpendPeriod::get());
+		assert_eq!(Treasury::approvals(ROOM).into_inner(), vec![proposal_id]);
+
+		run_to_block(2 * SpendPeriod::get());
+		System::assert_has_event(Event::Treasury(crate::Event::Awarded(
+			ROOM,
+			proposal_id,
+			NATIVE,
+			40,
+			CHARLIE,
+		)));
+		assert_eq!(Balances::free_balance(CHARLIE), 41);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(pot(ROOM), 60);
+		assert_eq!(room_free_amount(ROOM, NATIVE), 60);
+		assert!(Treasury::approvals(ROOM).is_empty());
+		assert_eq!(Treasury::proposals(ROOM, proposal_id), None);
 	});
 }
 
 #[test]
-fn minting_works() {
-	new_test_ext().execute_with(|| {
-		// Check that accumulate works when we have Some value in Dummy already.
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_eq!(Treasury::pot(), 100);
+fn spends_wait_for_their_room_to_have_the_funds() {
+	ExtBuilder::build().execute_with(|| {
+		fund(OTHER_ROOM, NATIVE, 100);
+		let proposal_id = approve(ROOM, NATIVE, 40);
+
+		run_to_block(2 * SpendPeriod::get());
+		assert_eq!(Treasury::approvals(ROOM).into_inner(), vec![proposal_id]);
+		assert_eq!(pot(OTHER_ROOM), 100);
+
+		fund(ROOM, NATIVE, 40);
+		run_to_block(3 * SpendPeriod::get());
+		assert!(Treasury::approvals(ROOM).is_empty());
+		assert_eq!(pot(ROOM), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), 41);
 	});
 }
 
 #[test]
-fn spend_proposal_takes_min_deposit() {
-	new_test_ext().execute_with(|| {
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1, 3));
-		assert_eq!(Balances::free_balance(0), 99);
-		assert_eq!(Balances::reserved_balance(0), 1);
+fn payouts_are_bounded_per_block() {
+	ExtBuilder::build().execute_with(|| {
+		for _ in 0..3 {
+			approve(ROOM, NATIVE, 10);
+		}
+		// nothing is paid in the first round, the room has no funds yet
+		run_to_block(SpendPeriod::get() + 1);
+		assert_eq!(Treasury::payout_round(), None);
+		fund(ROOM, NATIVE, 100);
+
+		run_to_block(2 * SpendPeriod::get());
+		assert_eq!(Balances::free_balance(CHARLIE), 21);
+		assert_eq!(Treasury::approvals(ROOM).len(), 1);
+		assert_eq!(Treasury::payout_round(), Some(Some((ROOM, 0))));
+
+		run_to_block(2 * SpendPeriod::get() + 1);
+		assert_eq!(Balances::free_balance(CHARLIE), 31);
+		assert!(Treasury::approvals(ROOM).is_empty());
+		assert_eq!(Treasury::payout_round(), None);
+
+		let events = treasury_events();
+		let spending =
+			events.iter().filter(|e| matches!(e, crate::Event::Spending(ROOM, ..))).count();
+		let rollover =
+			events.iter().filter(|e| matches!(e, crate::Event::Rollover(ROOM, ..))).count();
+		// one for each round
+		assert_eq!((spending, rollover), (2, 2));
 	});
 }
 
 #[test]
-fn spend_proposal_takes_proportional_deposit() {
-	new_test_ext().execute_with(|| {
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3));
-		assert_eq!(Balances::free_balance(0), 95);
-		assert_eq!(Balances::reserved_balance(0), 5);
+fn unpayable_approvals_do_not_stall_the_payout_round() {
+	ExtBuilder::build().execute_with(|| {
+		for _ in 0..MaxApprovals::get() {
+			approve(ROOM, NATIVE, 10);
+		}
+		fund(OTHER_ROOM, NATIVE, 10);
+		let proposal_id = approve(OTHER_ROOM, NATIVE, 10);
+
+		run_to_block(2 * SpendPeriod::get() + 2);
+		assert_eq!(Treasury::payout_round(), None);
+		assert_eq!(Treasury::proposals(OTHER_ROOM, proposal_id), None);
+		assert_eq!(Treasury::approvals(ROOM).len(), MaxApprovals::get() as usize);
+		assert_eq!(Balances::free_balance(CHARLIE), 11);
 	});
 }
 
 #[test]
-fn spend_proposal_fails_when_proposer_poor() {
-	new_test_ext().execute_with(|| {
+fn proposals_can_only_be_approved_once() {
+	ExtBuilder::build().execute_with(|| {
+		let proposal_id = approve(ROOM, NATIVE, 10);
+		System::set_block_number(3);
 		assert_noop!(
-			Treasury::propose_spend(Origin::signed(2), 100, 3),
-			Error::<Test, _>::InsufficientProposersBalance,
+			Treasury::approve_proposal(Origin::root(), ROOM, proposal_id),
+			Error::<Runtime>::AlreadyApproved
+		);
+		assert_eq!(Treasury::approvals(ROOM).into_inner(), vec![proposal_id]);
+		assert_eq!(
+			Treasury::proposals(ROOM, proposal_id).unwrap().start_spend_time,
+			Some(1 + SpendPeriod::get())
 		);
 	});
 }
 
 #[test]
-fn accepted_spend_proposal_ignored_outside_spend_period() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+fn claim_spend_pays_a_matured_spend() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, NATIVE, 100);
+		let unapproved = propose(ROOM, NATIVE, 10);
+		let proposal_id = approve(ROOM, NATIVE, 40);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3));
-		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_noop!(
+			Treasury::claim_spend(Origin::signed(ALICE), ROOM, proposal_id),
+			Error::<Runtime>::NotBeneficiaryOrProposer
+		);
+		assert_noop!(
+			Treasury::claim_spend(Origin::signed(BOB), ROOM, unapproved),
+			Error::<Runtime>::NotApproved
+		);
+		assert_noop!(
+			Treasury::claim_spend(Origin::signed(CHARLIE), ROOM, proposal_id),
+			Error::<Runtime>::NotMatured
+		);
 
-		<Treasury as OnInitialize<u64>>::on_initialize(1);
-		assert_eq!(Balances::free_balance(3), 0);
-		assert_eq!(Treasury::pot(), 100);
+		System::set_block_number(1 + SpendPeriod::get());
+		assert_ok!(Treasury::claim_spend(Origin::signed(CHARLIE), ROOM, proposal_id));
+		System::assert_last_event(Event::Treasury(crate::Event::Awarded(
+			ROOM,
+			proposal_id,
+			NATIVE,
+			40,
+			CHARLIE,
+		)));
+		assert_eq!(Balances::free_balance(CHARLIE), 41);
+		assert_eq!(pot(ROOM), 60);
+		assert!(Treasury::approvals(ROOM).is_empty());
+		assert_noop!(
+			Treasury::claim_spend(Origin::signed(CHARLIE), ROOM, proposal_id),
+			Error::<Runtime>::InvalidIndex
+		);
 	});
 }
 
 #[test]
-fn unused_pot_should_diminish() {
-	new_test_ext().execute_with(|| {
-		let init_total_issuance = Balances::total_issuance();
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_eq!(Balances::total_issuance(), init_total_issuance + 100);
-
-		<Treasury as OnInitialize<u64>>::on_initialize(2);
-		assert_eq!(Treasury::pot(), 50);
-		assert_eq!(Balances::total_issuance(), init_total_issuance + 50);
+fn claim_spend_fails_without_funds() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, NATIVE, 30);
+		let proposal_id = approve(ROOM, NATIVE, 40);
+
+		System::set_block_number(1 + SpendPeriod::get());
+		assert_noop!(
+			Treasury::claim_spend(Origin::signed(BOB), ROOM, proposal_id),
+			Error::<Runtime>::InsufficientRoomFunds
+		);
+		assert_eq!(Treasury::approvals(ROOM).into_inner(), vec![proposal_id]);
 	});
 }
 
 #[test]
-fn rejected_spend_proposal_ignored_on_spend_period() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+fn dissolution_pays_approved_spends_and_sweeps_the_pot() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, NATIVE, 100);
+		let approved = approve(ROOM, NATIVE, 40);
+		let unapproved = propose(ROOM, NATIVE, 10);
+		assert_eq!(Balances::reserved_balance(BOB), 3);
+
+		Treasury::remove_room_treasury_info(ROOM);
+		System::assert_has_event(Event::Treasury(crate::Event::Awarded(
+			ROOM, approved, NATIVE, 40, CHARLIE,
+		)));
+		System::assert_has_event(Event::Treasury(crate::Event::BondReleased(ROOM, unapproved, 1)));
+		System::assert_has_event(Event::Treasury(crate::Event::PotSwept(ROOM, NATIVE, 60)));
+		System::assert_last_event(Event::Treasury(crate::Event::RoomDissolved(ROOM)));
+		assert_eq!(Balances::free_balance(CHARLIE), 41);
+		assert_eq!(Balances::free_balance(RESERVE), 60);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(pot(ROOM), 0);
+		assert_eq!(room_free_amount(ROOM, NATIVE), 0);
+		assert_eq!(Treasury::dissolving_rooms(ROOM), None);
+		assert_eq!(Treasury::proposal_count(ROOM), 0);
+	});
+}
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3));
-		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+#[test]
+fn rooms_choose_their_dissolution_policy() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, NATIVE, 100);
+		let approved = approve(ROOM, NATIVE, 40);
 
-		<Treasury as OnInitialize<u64>>::on_initialize(2);
-		assert_eq!(Balances::free_balance(3), 0);
-		assert_eq!(Treasury::pot(), 50);
+		assert_noop!(
+			Treasury::set_dissolution_policy(
+				Origin::signed(ALICE),
+				ROOM,
+				Some(DissolutionPolicy::RefundApproved)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Treasury::set_dissolution_policy(
+				council(OTHER_ROOM),
+				ROOM,
+				Some(DissolutionPolicy::RefundApproved)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Treasury::set_dissolution_policy(
+			council(ROOM),
+			ROOM,
+			Some(DissolutionPolicy::RefundApproved)
+		));
+		System::assert_last_event(Event::Treasury(crate::Event::DissolutionPolicySet(
+			ROOM,
+			Some(DissolutionPolicy::RefundApproved),
+		)));
+
+		Treasury::remove_room_treasury_info(ROOM);
+		System::assert_has_event(Event::Treasury(crate::Event::SpendRefunded(ROOM, approved, 2)));
+		assert_eq!(Balances::free_balance(CHARLIE), 1);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(RESERVE), 100);
+		assert_eq!(Treasury::room_dissolution_policy(ROOM), None);
 	});
 }
 
 #[test]
-fn reject_already_rejected_spend_proposal_fails() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+fn dissolution_is_spread_over_blocks() {
+	ExtBuilder::build().execute_with(|| {
+		for _ in 0..5 {
+			propose(ROOM, NATIVE, 10);
+		}
+
+		Treasury::remove_room_treasury_info(ROOM);
+		assert_eq!(Balances::reserved_balance(BOB), 3);
+		assert!(Treasury::dissolving_rooms(ROOM).is_some());
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(BOB), ROOM, NATIVE, 10, CHARLIE, None),
+			Error::<Runtime>::RoomDissolving
+		);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3));
-		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
-		assert_noop!(Treasury::reject_proposal(Origin::root(), 0), Error::<Test, _>::InvalidIndex);
+		run_to_block(2);
+		assert_eq!(Balances::reserved_balance(BOB), 1);
+		run_to_block(3);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		System::assert_last_event(Event::Treasury(crate::Event::RoomDissolved(ROOM)));
+		assert_eq!(Treasury::dissolving_rooms(ROOM), None);
 	});
 }
 
 #[test]
-fn reject_non_existent_spend_proposal_fails() {
-	new_test_ext().execute_with(|| {
-		assert_noop!(Treasury::reject_proposal(Origin::root(), 0), Error::<Test, _>::InvalidIndex);
+fn spends_are_paid_in_their_currency() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, TOKEN, 100);
+		assert_eq!(Tokens::free_balance(TOKEN, &Treasury::room_account_id(ROOM)), 100);
+		assert_eq!(Treasury::room_currency_funds(ROOM, TOKEN), 100);
+		assert_eq!(room_free_amount(ROOM, NATIVE), 0);
+
+		let proposal_id = approve(ROOM, TOKEN, 40);
+		// the bond is held in the native currency
+		assert_eq!(Balances::reserved_balance(BOB), 2);
+		assert_eq!(Tokens::reserved_balance(TOKEN, &BOB), 0);
+
+		run_to_block(2 * SpendPeriod::get());
+		System::assert_has_event(Event::Treasury(crate::Event::Spending(ROOM, TOKEN, 100)));
+		System::assert_has_event(Event::Treasury(crate::Event::Awarded(
+			ROOM,
+			proposal_id,
+			TOKEN,
+			40,
+			CHARLIE,
+		)));
+		System::assert_has_event(Event::Treasury(crate::Event::Rollover(ROOM, TOKEN, 60)));
+		assert_eq!(Tokens::free_balance(TOKEN, &CHARLIE), 40);
+		assert_eq!(Balances::free_balance(CHARLIE), 1);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(room_free_amount(ROOM, TOKEN), 60);
 	});
 }
 
 #[test]
-fn accept_non_existent_spend_proposal_fails() {
-	new_test_ext().execute_with(|| {
-		assert_noop!(Treasury::approve_proposal(Origin::root(), 0), Error::<Test, _>::InvalidIndex);
+fn bonds_can_be_held_in_the_spend_currency() {
+	ExtBuilder::build().execute_with(|| {
+		BondInSpendCurrency::set(true);
+		let proposal_id = propose(ROOM, TOKEN, 40);
+		assert_eq!(Tokens::reserved_balance(TOKEN, &BOB), 2);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), ROOM, proposal_id));
+		System::assert_last_event(Event::Treasury(crate::Event::Rejected(proposal_id, 2)));
+		assert_eq!(Tokens::total_balance(TOKEN, &BOB), 98);
+		assert_eq!(Balances::free_balance(BOB), 100);
 	});
 }
 
 #[test]
-fn accept_already_rejected_spend_proposal_fails() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+fn only_native_spends_can_be_scheduled() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = PayoutSchedule { start_offset: 0, period: 2, period_count: 4 };
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(BOB), ROOM, TOKEN, 40, CHARLIE, Some(schedule)),
+			Error::<Runtime>::ScheduleNotNative
+		);
+	});
+}
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3));
-		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
-		assert_noop!(Treasury::approve_proposal(Origin::root(), 0), Error::<Test, _>::InvalidIndex);
+#[test]
+fn migration_to_v1_records_the_native_currency() {
+	ExtBuilder::build().execute_with(|| {
+		use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+		// proposer, value, beneficiary, bond, start_spend_time
+		let old = (BOB, 40u64, CHARLIE, 2u64, Some(6u64));
+		frame_support::storage::unhashed::put(&Proposals::<Runtime>::hashed_key_for(ROOM, 0), &old);
+		StorageVersion::new(0).put::<Treasury>();
+
+		migrations::migrate::<Runtime>();
+		let proposal = Treasury::proposals(ROOM, 0).unwrap();
+		assert_eq!((proposal.value, proposal.bond), (40, 2));
+		assert_eq!(proposal.start_spend_time, Some(6));
+		assert_eq!((proposal.currency_id, proposal.bond_currency_id), (NATIVE, NATIVE));
+		assert_eq!(Treasury::on_chain_storage_version(), 1);
 	});
 }
 
+fn propose_scheduled(value: Balance, period_count: u32) -> ProposalIndex {
+	let schedule = PayoutSchedule { start_offset: 2, period: 2, period_count };
+	assert_ok!(Treasury::propose_spend(
+		Origin::signed(BOB),
+		ROOM,
+		NATIVE,
+		value,
+		CHARLIE,
+		Some(schedule)
+	));
+	Treasury::proposal_count(ROOM) - 1
+}
+
 #[test]
-fn accepted_spend_proposal_enacted_on_spend_period() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_eq!(Treasury::pot(), 100);
+fn scheduled_spends_are_vested() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, NATIVE, 100);
+		let proposal_id = propose_scheduled(40, 4);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), ROOM, proposal_id));
+
+		run_to_block(2 * SpendPeriod::get());
+		let grant = Treasury::grants(ROOM, proposal_id).unwrap();
+		assert_eq!(
+			(grant.start, grant.period, grant.period_count, grant.per_period),
+			(12, 2, 4, 10)
+		);
+		assert_eq!(Balances::free_balance(CHARLIE), 41);
+		assert_eq!(Balances::usable_balance(&CHARLIE), 1);
+		assert_eq!(Treasury::spend_schedules(ROOM, proposal_id), None);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3));
-		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		// one release has vested by block 14
+		run_to_block(14);
+		assert_noop!(
+			Treasury::cancel_grant(Origin::signed(ALICE), ROOM, proposal_id),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Treasury::cancel_grant(Origin::root(), ROOM, proposal_id));
+		System::assert_last_event(Event::Treasury(crate::Event::GrantCancelled(
+			ROOM,
+			proposal_id,
+			30,
+		)));
+		assert_eq!(Balances::free_balance(CHARLIE), 11);
+		assert_eq!(pot(ROOM), 90);
+		assert_eq!(room_free_amount(ROOM, NATIVE), 90);
+		assert_eq!(Treasury::grants(ROOM, proposal_id), None);
+		assert_noop!(
+			Treasury::cancel_grant(Origin::root(), ROOM, proposal_id),
+			Error::<Runtime>::NoGrant
+		);
+	});
+}
 
-		<Treasury as OnInitialize<u64>>::on_initialize(2);
-		assert_eq!(Balances::free_balance(3), 100);
-		assert_eq!(Treasury::pot(), 0);
+#[test]
+fn vested_grants_can_be_pruned() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, NATIVE, 100);
+		let proposal_id = propose_scheduled(40, 4);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), ROOM, proposal_id));
+		run_to_block(2 * SpendPeriod::get());
+
+		run_to_block(19);
+		assert_noop!(
+			Treasury::prune_grant(Origin::signed(ALICE), ROOM, proposal_id),
+			Error::<Runtime>::GrantNotVested
+		);
+		run_to_block(20);
+		assert_ok!(Treasury::prune_grant(Origin::signed(ALICE), ROOM, proposal_id));
+		System::assert_last_event(Event::Treasury(crate::Event::GrantPruned(ROOM, proposal_id)));
+		assert_eq!(Treasury::grants(ROOM, proposal_id), None);
 	});
 }
 
 #[test]
-fn pot_underflow_should_not_diminish() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_eq!(Treasury::pot(), 100);
+fn schedules_must_suit_the_vesting_pallet() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = |period_count| PayoutSchedule { start_offset: 0, period: 2, period_count };
+		assert_noop!(
+			Treasury::propose_spend(
+				Origin::signed(BOB),
+				ROOM,
+				NATIVE,
+				40,
+				CHARLIE,
+				Some(schedule(3))
+			),
+			Error::<Runtime>::InvalidSchedule
+		);
+		assert_noop!(
+			Treasury::propose_spend(
+				Origin::signed(BOB),
+				ROOM,
+				NATIVE,
+				3,
+				CHARLIE,
+				Some(schedule(3))
+			),
+			orml_vesting::Error::<Runtime>::AmountLow
+		);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 150, 3));
-		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		for _ in 0..MaxVestingSchedules::get() {
+			assert_ok!(Vesting::vested_transfer(
+				Origin::signed(ALICE),
+				CHARLIE,
+				orml_vesting::VestingSchedule {
+					start: 0,
+					period: 2,
+					period_count: 2,
+					per_period: 2
+				}
+			));
+		}
+		assert_noop!(
+			Treasury::propose_spend(
+				Origin::signed(BOB),
+				ROOM,
+				NATIVE,
+				40,
+				CHARLIE,
+				Some(schedule(4))
+			),
+			orml_vesting::Error::<Runtime>::MaxVestingSchedulesExceeded
+		);
+	});
+}
 
-		<Treasury as OnInitialize<u64>>::on_initialize(2);
-		assert_eq!(Treasury::pot(), 100); // Pot hasn't changed
+#[test]
+fn only_the_room_sets_its_budget() {
+	ExtBuilder::build().execute_with(|| {
+		assert_noop!(
+			Treasury::set_room_budget(Origin::root(), ROOM, Some(50), None),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Treasury::set_room_budget(council(OTHER_ROOM), ROOM, Some(50), None),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Treasury::set_room_budget(council(ROOM), ROOM, Some(50), None));
+		System::assert_last_event(Event::Treasury(crate::Event::BudgetSet(ROOM, Some(50), None)));
+		assert_eq!(
+			Treasury::room_budgets(ROOM),
+			Some(RoomBudget { max_spend: Some(50), burn: None })
+		);
 
-		let _ = Balances::deposit_into_existing(&Treasury::account_id(), 100).unwrap();
-		<Treasury as OnInitialize<u64>>::on_initialize(4);
-		assert_eq!(Balances::free_balance(3), 150); // Fund has been spent
-		assert_eq!(Treasury::pot(), 25); // Pot has finally changed
+		assert_ok!(Treasury::set_room_budget(council(ROOM), ROOM, None, None));
+		assert_eq!(Treasury::room_budgets(ROOM), None);
 	});
 }
 
-// Treasury account doesn't get deleted if amount approved to spend is all its free balance.
-// i.e. pot should not include existential deposit needed for account survival.
 #[test]
-fn treasury_account_doesnt_get_deleted() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_eq!(Treasury::pot(), 100);
-		let treasury_balance = Balances::free_balance(&Treasury::account_id());
-
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), treasury_balance, 3));
-		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+fn the_budget_applies_per_period() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, NATIVE, 100);
+		assert_ok!(Treasury::set_room_budget(council(ROOM), ROOM, Some(50), None));
+		approve(ROOM, NATIVE, 30);
+		let proposal_id = propose(ROOM, NATIVE, 30);
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), ROOM, proposal_id),
+			Error::<Runtime>::BudgetExceeded
+		);
+		// spends in other currencies are not limited
+		approve(ROOM, TOKEN, 30);
 
-		<Treasury as OnInitialize<u64>>::on_initialize(2);
-		assert_eq!(Treasury::pot(), 100); // Pot hasn't changed
+		// approved now, the spend matures in the next period
+		System::set_block_number(1 + SpendPeriod::get());
+		assert_ok!(Treasury::approve_proposal(Origin::root(), ROOM, proposal_id));
+	});
+}
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), Treasury::pot(), 3));
-		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+#[test]
+fn burns_leave_what_approvals_need() {
+	ExtBuilder::build().execute_with(|| {
+		fund(ROOM, NATIVE, 100);
+		assert_ok!(Treasury::set_room_budget(
+			council(ROOM),
+			ROOM,
+			None,
+			Some(Permill::from_percent(50))
+		));
+		approve(ROOM, NATIVE, 40);
+
+		// the payout round comes before the spend matures, the burn round right after it
+		run_to_block(SpendPeriod::get() + 1);
+		System::assert_has_event(Event::Treasury(crate::Event::Burnt(ROOM, 30)));
+		assert_eq!(pot(ROOM), 70);
+		assert_eq!(room_free_amount(ROOM, NATIVE), 70);
+		assert_eq!(Treasury::approvals(ROOM).len(), 1);
+	});
+}
 
-		<Treasury as OnInitialize<u64>>::on_initialize(4);
-		assert_eq!(Treasury::pot(), 0); // Pot is emptied
-		assert_eq!(Balances::free_balance(Treasury::account_id()), 1); // but the account is still there
+#[test]
+fn unapproved_proposals_expire() {
+	ExtBuilder::build().execute_with(|| {
+		let proposal_id = propose(ROOM, NATIVE, 200);
+		assert_eq!(Balances::reserved_balance(BOB), 10);
+
+		run_to_block(ProposalLifetime::get());
+		assert!(Treasury::proposals(ROOM, proposal_id).is_some());
+
+		run_to_block(1 + ProposalLifetime::get());
+		System::assert_has_event(Event::Treasury(crate::Event::ProposalExpired(
+			ROOM,
+			proposal_id,
+			1,
+		)));
+		assert_eq!(Treasury::proposals(ROOM, proposal_id), None);
+		assert_eq!(Treasury::proposed_at(ROOM, proposal_id), None);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 99);
 	});
 }
 
-// In case treasury account is not existing then it works fine.
-// This is useful for chain that will just update runtime.
 #[test]
-fn inexistent_account_works() {
-	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
-	pallet_balances::GenesisConfig::<Test> { balances: vec![(0, 100), (1, 99), (2, 1)] }
-		.assimilate_storage(&mut t)
-		.unwrap();
-	// Treasury genesis config is not build thus treasury account does not exist
-	let mut t: sp_io::TestExternalities = t.into();
-
-	t.execute_with(|| {
-		assert_eq!(Balances::free_balance(Treasury::account_id()), 0); // Account does not exist
-		assert_eq!(Treasury::pot(), 0); // Pot is empty
-
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 99, 3));
-		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1, 3));
-		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
-		<Treasury as OnInitialize<u64>>::on_initialize(2);
-		assert_eq!(Treasury::pot(), 0); // Pot hasn't changed
-		assert_eq!(Balances::free_balance(3), 0); // Balance of `3` hasn't changed
-
-		Balances::make_free_balance_be(&Treasury::account_id(), 100);
-		assert_eq!(Treasury::pot(), 99); // Pot now contains funds
-		assert_eq!(Balances::free_balance(Treasury::account_id()), 100); // Account does exist
-
-		<Treasury as OnInitialize<u64>>::on_initialize(4);
-
-		assert_eq!(Treasury::pot(), 0); // Pot has changed
-		assert_eq!(Balances::free_balance(3), 99); // Balance of `3` has changed
+fn unpaid_spends_lapse_after_the_claim_window() {
+	ExtBuilder::build().execute_with(|| {
+		let proposal_id = approve(ROOM, NATIVE, 40);
+		let matured = Treasury::proposals(ROOM, proposal_id).unwrap().start_spend_time.unwrap();
+
+		run_to_block(matured + ClaimWindow::get() - 1);
+		assert_eq!(Treasury::approvals(ROOM).into_inner(), vec![proposal_id]);
+
+		run_to_block(matured + ClaimWindow::get());
+		System::assert_has_event(Event::Treasury(crate::Event::SpendLapsed(ROOM, proposal_id)));
+		assert_eq!(Treasury::proposals(ROOM, proposal_id), None);
+		assert!(Treasury::approvals(ROOM).is_empty());
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 100);
 	});
 }
 
 #[test]
-fn genesis_funding_works() {
-	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
-	let initial_funding = 100;
-	pallet_balances::GenesisConfig::<Test> {
-		// Total issuance will be 200 with treasury account initialized with 100.
-		balances: vec![(0, 100), (Treasury::account_id(), initial_funding)],
-	}
-	.assimilate_storage(&mut t)
-	.unwrap();
-	treasury::GenesisConfig::default()
-		.assimilate_storage::<Test, _>(&mut t)
-		.unwrap();
-	let mut t: sp_io::TestExternalities = t.into();
-
-	t.execute_with(|| {
-		assert_eq!(Balances::free_balance(Treasury::account_id()), initial_funding);
-		assert_eq!(Treasury::pot(), initial_funding - Balances::minimum_balance());
+fn rejecting_an_approved_proposal_drops_its_approval() {
+	ExtBuilder::build().execute_with(|| {
+		let rejected = approve(ROOM, NATIVE, 40);
+		let kept = approve(ROOM, NATIVE, 20);
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), ROOM, rejected));
+		assert_eq!(Treasury::approvals(ROOM).into_inner(), vec![kept]);
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), ROOM, kept));
+		assert!(!Approvals::<Runtime>::contains_key(ROOM));
 	});
 }
@@ -23,6 +23,11 @@
 //! - `claim` - Claim unlocked balances.
 //! - `update_vesting_schedules` - Update all vesting schedules under an
 //!   account, `root` origin required.
+//!
+//! ### Other Pallets
+//!
+//! The module implements `VestedTransfer`, which lets other pallets add a
+//! vesting schedule and revoke it, taking back what it still locks.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -38,6 +43,7 @@ use frame_support::{
 	transactional, BoundedVec,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
+use listen_primitives::traits::VestedTransfer;
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{AtLeast32Bit, BlockNumberProvider, CheckedAdd, Saturating, StaticLookup, Zero},
@@ -174,6 +180,8 @@ pub mod module {
 		Claimed { who: T::AccountId, amount: BalanceOf<T> },
 		/// Updated vesting schedules.
 		VestingSchedulesUpdated { who: T::AccountId },
+		/// Revoked a vesting schedule, returning its locked balance.
+		VestingScheduleRevoked { who: T::AccountId, to: T::AccountId, amount: BalanceOf<T> },
 	}
 
 	/// Vesting schedules of an account.
@@ -355,6 +363,34 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Removes `schedule` from `who` and transfers what it still locks to `to`.
+	#[transactional]
+	fn do_revoke_vested_transfer(
+		who: &T::AccountId,
+		to: &T::AccountId,
+		schedule: VestingScheduleOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let now = T::BlockNumberProvider::current_block_number();
+		let mut schedules = <VestingSchedules<T>>::get(who);
+		// fully vested schedules are already dropped when the locked balance is read
+		let unvested = match schedules.iter().position(|s| s == &schedule) {
+			Some(index) => schedules.remove(index).locked_amount(now),
+			None => return Ok(Zero::zero()),
+		};
+		<VestingSchedules<T>>::insert(who, schedules);
+		Self::do_claim(who);
+
+		if !unvested.is_zero() {
+			T::Currency::transfer(who, to, unvested, ExistenceRequirement::AllowDeath)?;
+		}
+		Self::deposit_event(Event::VestingScheduleRevoked {
+			who: who.clone(),
+			to: to.clone(),
+			amount: unvested,
+		});
+		Ok(unvested)
+	}
+
 	fn do_update_vesting_schedules(
 		who: &T::AccountId,
 		schedules: Vec<VestingScheduleOf<T>>,
@@ -389,6 +425,65 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> VestedTransfer<T::AccountId, T::BlockNumber, BalanceOf<T>, DispatchError>
+	for Pallet<T>
+{
+	fn vested_transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		start: T::BlockNumber,
+		period: T::BlockNumber,
+		period_count: u32,
+		per_period: BalanceOf<T>,
+	) -> DispatchResult {
+		let schedule = VestingSchedule { start, period, period_count, per_period };
+		Self::do_vested_transfer(from, to, schedule.clone())?;
+
+		Self::deposit_event(Event::VestingScheduleAdded {
+			from: from.clone(),
+			to: to.clone(),
+			vesting_schedule: schedule,
+		});
+		Ok(())
+	}
+
+	fn revoke_vested_transfer(
+		who: &T::AccountId,
+		to: &T::AccountId,
+		start: T::BlockNumber,
+		period: T::BlockNumber,
+		period_count: u32,
+		per_period: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		Self::do_revoke_vested_transfer(
+			who,
+			to,
+			VestingSchedule { start, period, period_count, per_period },
+		)
+	}
+
+	fn ensure_can_vested_transfer(
+		to: &T::AccountId,
+		start: T::BlockNumber,
+		period: T::BlockNumber,
+		period_count: u32,
+		per_period: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure_valid_vesting_schedule::<T>(&VestingSchedule {
+			start,
+			period,
+			period_count,
+			per_period,
+		})?;
+		ensure!(
+			<VestingSchedules<T>>::decode_len(to).unwrap_or_default() <
+				T::MaxVestingSchedules::get() as usize,
+			Error::<T>::MaxVestingSchedulesExceeded
+		);
+		Ok(())
+	}
+}
+
 /// Returns `Ok(total_total)` if valid schedule, or error.
 fn ensure_valid_vesting_schedule<T: Config>(
 	schedule: &VestingScheduleOf<T>,
@@ -350,3 +350,52 @@ fn cliff_vesting_works() {
 		assert_ok!(PalletBalances::transfer(Origin::signed(BOB), CHARLIE, VESTING_AMOUNT));
 	});
 }
+
+#[test]
+fn revoke_vested_transfer_returns_locked_balance() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(<Vesting as VestedTransfer<_, _, _, _>>::vested_transfer(
+			&ALICE, &BOB, 0u64, 10u64, 2u32, 10u64
+		));
+		assert_eq!(PalletBalances::free_balance(BOB), 20);
+
+		MockBlockNumberProvider::set(10);
+		assert_eq!(
+			<Vesting as VestedTransfer<_, _, _, _>>::revoke_vested_transfer(
+				&BOB, &ALICE, 0u64, 10u64, 2u32, 10u64
+			),
+			Ok(10)
+		);
+		System::assert_last_event(Event::Vesting(crate::Event::VestingScheduleRevoked {
+			who: BOB,
+			to: ALICE,
+			amount: 10,
+		}));
+		assert_eq!(PalletBalances::free_balance(ALICE), 90);
+		assert_eq!(PalletBalances::free_balance(BOB), 10);
+		assert!(Vesting::vesting_schedules(&BOB).is_empty());
+		assert_eq!(PalletBalances::locks(&BOB), vec![]);
+	});
+}
+
+#[test]
+fn ensure_can_vested_transfer_checks_the_limits() {
+	ExtBuilder::build().execute_with(|| {
+		let check = |who| {
+			<Vesting as VestedTransfer<_, _, _, _>>::ensure_can_vested_transfer(
+				&who, 0u64, 10u64, 2u32, 10u64,
+			)
+		};
+		assert_ok!(check(BOB));
+		assert_noop!(
+			<Vesting as VestedTransfer<_, _, _, _>>::ensure_can_vested_transfer(
+				&BOB, 0u64, 10u64, 2u32, 2u64
+			),
+			Error::<Runtime>::AmountLow
+		);
+		// CHARLIE already has `MaxVestingSchedules` schedules
+		assert_noop!(check(CHARLIE), Error::<Runtime>::MaxVestingSchedulesExceeded);
+	});
+}
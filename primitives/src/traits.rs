@@ -9,13 +9,199 @@ pub trait ListenHandler<RoomIndex, AccountId, DispatchErr, Balance> {
 	fn get_room_free_amount(room_id: RoomIndex) -> Balance;
 	fn sub_room_free_amount(room_id: RoomIndex, amount: Balance)
 		-> result::Result<(), DispatchErr>;
+	fn add_room_free_amount(room_id: RoomIndex, amount: Balance)
+		-> result::Result<(), DispatchErr>;
+	fn exit_room_council(
+		room_id: RoomIndex,
+		who: AccountId,
+		payout: Balance,
+	) -> result::Result<(), DispatchErr>;
+	fn is_in_room(room_id: RoomIndex, who: AccountId) -> result::Result<bool, DispatchErr>;
+	fn set_room_council(
+		room_id: RoomIndex,
+		council: Vec<AccountId>,
+	) -> result::Result<(), DispatchErr>;
+}
+
+pub trait MultiCurrencyListenHandler<RoomIndex, AccountId, CurrencyId, DispatchErr, Balance>:
+	ListenHandler<RoomIndex, AccountId, DispatchErr, Balance>
+{
+	fn get_room_currency_free_amount(room_id: RoomIndex, currency_id: CurrencyId) -> Balance;
+	fn add_room_currency_free_amount(
+		room_id: RoomIndex,
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> result::Result<(), DispatchErr>;
+	fn sub_room_currency_free_amount(
+		room_id: RoomIndex,
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> result::Result<(), DispatchErr>;
 }
 
 pub trait CollectiveHandler<RoomIndex, BlockNumber, DispatchErr> {
-	fn remove_room_collective_info(room_id: RoomIndex) -> result::Result<(), DispatchErr>;
+	fn remove_room_collective_info(room_id: RoomIndex) -> result::Result<u64, DispatchErr>;
 	fn get_motion_duration(room_id: RoomIndex) -> BlockNumber;
 }
 
+pub trait EscalationHandler<RoomIndex, Motion, DispatchErr> {
+	fn escalate(
+		room_id: RoomIndex,
+		source_index: u32,
+		motion: Motion,
+		threshold: u32,
+	) -> result::Result<u32, DispatchErr>;
+}
+
+impl<RoomIndex, Motion, DispatchErr: From<&'static str>>
+	EscalationHandler<RoomIndex, Motion, DispatchErr> for ()
+{
+	fn escalate(
+		_room_id: RoomIndex,
+		_source_index: u32,
+		_motion: Motion,
+		_threshold: u32,
+	) -> result::Result<u32, DispatchErr> {
+		Err("escalation is not supported".into())
+	}
+}
+
+/// How a motion of a room closed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MotionOutcome {
+	/// The council approved the motion.
+	Approved,
+	/// The council rejected the motion, or let it expire.
+	Rejected,
+	/// The motion was closed over the head of the council, by a veto or by root.
+	Overruled,
+}
+
+pub trait GovernanceObserver<RoomIndex, AccountId> {
+	fn on_motion_closed(
+		room_id: RoomIndex,
+		proposer: Option<AccountId>,
+		outcome: MotionOutcome,
+		ayes: &[AccountId],
+		nays: &[AccountId],
+		abstentions: &[AccountId],
+	);
+}
+
+impl<RoomIndex, AccountId> GovernanceObserver<RoomIndex, AccountId> for () {
+	fn on_motion_closed(
+		_room_id: RoomIndex,
+		_proposer: Option<AccountId>,
+		_outcome: MotionOutcome,
+		_ayes: &[AccountId],
+		_nays: &[AccountId],
+		_abstentions: &[AccountId],
+	) {
+	}
+}
+
+pub trait VoteWeightSource<RoomIndex, AccountId> {
+	fn vote_weight(room_id: RoomIndex, who: &AccountId) -> u64;
+}
+
+impl<RoomIndex, AccountId> VoteWeightSource<RoomIndex, AccountId> for () {
+	fn vote_weight(_room_id: RoomIndex, _who: &AccountId) -> u64 {
+		1
+	}
+}
+
+pub trait PendingSpends<RoomIndex, Balance> {
+	fn pending_spends(room_id: RoomIndex) -> Balance;
+}
+
+impl<RoomIndex, Balance: Default> PendingSpends<RoomIndex, Balance> for () {
+	fn pending_spends(_room_id: RoomIndex) -> Balance {
+		Default::default()
+	}
+}
+
+/// Settles the funds and deposits a pallet keeps for a room when the room is dissolved.
+///
+/// Tuples call their members in order; put the treasury last, as it sweeps the room's pot.
 pub trait RoomTreasuryHandler<RoomIndex> {
-	fn remove_room_treasury_info(room_id: RoomIndex);
+	/// Called while the room's free amount can still be spent from, so it must run before the
+	/// room's free amount is cleared. Returns the weight consumed.
+	fn remove_room_treasury_info(room_id: RoomIndex) -> u64;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<RoomIndex: Clone> RoomTreasuryHandler<RoomIndex> for Tuple {
+	fn remove_room_treasury_info(room_id: RoomIndex) -> u64 {
+		let mut weight = 0u64;
+		for_tuples!( #(
+			weight = weight.saturating_add(Tuple::remove_room_treasury_info(room_id.clone()));
+		)* );
+		weight
+	}
+}
+
+pub trait VestedTransfer<AccountId, BlockNumber, Balance, DispatchErr> {
+	fn vested_transfer(
+		from: &AccountId,
+		to: &AccountId,
+		start: BlockNumber,
+		period: BlockNumber,
+		period_count: u32,
+		per_period: Balance,
+	) -> result::Result<(), DispatchErr>;
+
+	fn revoke_vested_transfer(
+		who: &AccountId,
+		to: &AccountId,
+		start: BlockNumber,
+		period: BlockNumber,
+		period_count: u32,
+		per_period: Balance,
+	) -> result::Result<Balance, DispatchErr>;
+
+	/// Whether `to` could be given such a schedule now, leaving the sender's balance aside.
+	fn ensure_can_vested_transfer(
+		to: &AccountId,
+		start: BlockNumber,
+		period: BlockNumber,
+		period_count: u32,
+		per_period: Balance,
+	) -> result::Result<(), DispatchErr>;
+}
+
+/// No vesting: every schedule is refused.
+impl<AccountId, BlockNumber, Balance, DispatchErr: From<&'static str>>
+	VestedTransfer<AccountId, BlockNumber, Balance, DispatchErr> for ()
+{
+	fn vested_transfer(
+		_from: &AccountId,
+		_to: &AccountId,
+		_start: BlockNumber,
+		_period: BlockNumber,
+		_period_count: u32,
+		_per_period: Balance,
+	) -> result::Result<(), DispatchErr> {
+		Err("no vesting".into())
+	}
+
+	fn revoke_vested_transfer(
+		_who: &AccountId,
+		_to: &AccountId,
+		_start: BlockNumber,
+		_period: BlockNumber,
+		_period_count: u32,
+		_per_period: Balance,
+	) -> result::Result<Balance, DispatchErr> {
+		Err("no vesting".into())
+	}
+
+	fn ensure_can_vested_transfer(
+		_to: &AccountId,
+		_start: BlockNumber,
+		_period: BlockNumber,
+		_period_count: u32,
+		_per_period: Balance,
+	) -> result::Result<(), DispatchErr> {
+		Err("no vesting".into())
+	}
 }